@@ -0,0 +1,262 @@
+use std::{
+    error::Error,
+    fmt,
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use common::{Json, Solution};
+
+/// Shortest, median and longest of a set of timings.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(mut samples: Vec<Duration>) -> Stats {
+        samples.sort();
+
+        Stats {
+            min: samples.first().copied().unwrap_or_default(),
+            median: samples.get(samples.len() / 2).copied().unwrap_or_default(),
+            max: samples.last().copied().unwrap_or_default(),
+        }
+    }
+
+    fn to_json(self) -> Json {
+        Json::object([
+            ("min_ns", Json::from(self.min.as_nanos() as u64)),
+            ("median_ns", Json::from(self.median.as_nanos() as u64)),
+            ("max_ns", Json::from(self.max.as_nanos() as u64)),
+        ])
+    }
+
+    fn from_json(json: &Json) -> Option<Stats> {
+        let nanos = |key| {
+            json.get(key)
+                .and_then(Json::as_u64)
+                .map(Duration::from_nanos)
+        };
+
+        Some(Stats {
+            min: nanos("min_ns")?,
+            median: nanos("median_ns")?,
+            max: nanos("max_ns")?,
+        })
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "min {:>10.3?}  median {:>10.3?}  max {:>10.3?}",
+            self.min, self.median, self.max
+        )
+    }
+}
+
+/// Times `iterations` parses of `content` and as many solves of `part`,
+/// each phase on its own.
+pub fn measure<S: Solution>(
+    content: &str,
+    part: u32,
+    iterations: usize,
+) -> Result<(Stats, Stats), Box<dyn Error>> {
+    let mut parse_samples = Vec::with_capacity(iterations);
+    let mut solve_samples = Vec::with_capacity(iterations);
+
+    for _ in 0..iterations.max(1) {
+        let start = Instant::now();
+        let solution = S::parse(black_box(content))?;
+        parse_samples.push(start.elapsed());
+
+        let start = Instant::now();
+        match part {
+            1 => drop(black_box(solution.part_one())),
+            2 => drop(black_box(solution.part_two())),
+            _ => return Err(format!("no part {}, parts are 1 and 2", part).into()),
+        }
+        solve_samples.push(start.elapsed());
+    }
+
+    Ok((
+        Stats::from_samples(parse_samples),
+        Stats::from_samples(solve_samples),
+    ))
+}
+
+/// Timings of one part of a day on one input.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Measurement {
+    pub day: u32,
+    pub part: u32,
+    pub input: String,
+    pub lines: usize,
+    pub parse: Stats,
+    pub solve: Stats,
+}
+
+impl Measurement {
+    pub fn median(&self) -> Duration {
+        self.parse.median + self.solve.median
+    }
+
+    /// Input lines parsed and solved per second, at the median.
+    pub fn throughput(&self) -> f64 {
+        match self.median().as_secs_f64() {
+            seconds if seconds > 0.0 => self.lines as f64 / seconds,
+            _ => 0.0,
+        }
+    }
+
+    fn to_json(&self) -> Json {
+        Json::object([
+            ("day", Json::from(self.day)),
+            ("part", Json::from(self.part)),
+            ("input", Json::from(self.input.as_str())),
+            ("lines", Json::from(self.lines)),
+            ("parse", self.parse.to_json()),
+            ("solve", self.solve.to_json()),
+        ])
+    }
+
+    fn from_json(json: &Json) -> Option<Measurement> {
+        let number = |key| json.get(key).and_then(Json::as_u64);
+
+        Some(Measurement {
+            day: number("day")? as u32,
+            part: number("part")? as u32,
+            input: json.get("input")?.as_str()?.to_string(),
+            lines: number("lines")? as usize,
+            parse: Stats::from_json(json.get("parse")?)?,
+            solve: Stats::from_json(json.get("solve")?)?,
+        })
+    }
+
+    /// Relative change of the median against `baseline`, in percent.
+    pub fn change_from(&self, baseline: &Measurement) -> f64 {
+        let before = baseline.median().as_secs_f64();
+
+        match before {
+            before if before > 0.0 => (self.median().as_secs_f64() - before) / before * 100.0,
+            _ => 0.0,
+        }
+    }
+}
+
+impl fmt::Display for Measurement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "day {} part {} {} ({} lines)",
+            self.day, self.part, self.input, self.lines
+        )?;
+        writeln!(f, "  parse  {}", self.parse)?;
+        writeln!(f, "  solve  {}", self.solve)?;
+        write!(f, "  throughput {:.0} lines/sec", self.throughput())
+    }
+}
+
+/// A saved set of measurements to compare later runs with.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Baseline {
+    pub iterations: usize,
+    pub measurements: Vec<Measurement>,
+}
+
+impl Baseline {
+    pub fn find(&self, day: u32, part: u32, input: &str) -> Option<&Measurement> {
+        self.measurements.iter().find(|measurement| {
+            measurement.day == day && measurement.part == part && measurement.input == input
+        })
+    }
+
+    pub fn to_json(&self) -> Json {
+        Json::object([
+            ("iterations", Json::from(self.iterations)),
+            (
+                "measurements",
+                Json::Array(self.measurements.iter().map(Measurement::to_json).collect()),
+            ),
+        ])
+    }
+
+    pub fn from_json(json: &Json) -> Result<Baseline, String> {
+        let invalid = || "invalid baseline: expected iterations and measurements".to_string();
+
+        let iterations = json
+            .get("iterations")
+            .and_then(Json::as_u64)
+            .ok_or_else(invalid)? as usize;
+        let measurements = json
+            .get("measurements")
+            .and_then(Json::as_array)
+            .ok_or_else(invalid)?
+            .iter()
+            .map(|measurement| Measurement::from_json(measurement).ok_or_else(invalid))
+            .collect::<Result<Vec<Measurement>, String>>()?;
+
+        Ok(Baseline {
+            iterations,
+            measurements,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats() {
+        let samples = [5, 1, 4, 2, 3].map(Duration::from_millis).to_vec();
+
+        assert_eq!(
+            Stats::from_samples(samples),
+            Stats {
+                min: Duration::from_millis(1),
+                median: Duration::from_millis(3),
+                max: Duration::from_millis(5),
+            }
+        );
+        assert_eq!(Stats::from_samples(Vec::new()).max, Duration::ZERO);
+    }
+
+    #[test]
+    fn test_baseline_json() {
+        let stats = |median| Stats {
+            min: Duration::from_micros(median - 1),
+            median: Duration::from_micros(median),
+            max: Duration::from_micros(median + 1),
+        };
+        let baseline = Baseline {
+            iterations: 10,
+            measurements: vec![Measurement {
+                day: 3,
+                part: 2,
+                input: "input1.txt".to_string(),
+                lines: 140,
+                parse: stats(1500),
+                solve: stats(500),
+            }],
+        };
+
+        let json: Json = baseline.to_json().to_string().parse().unwrap();
+        assert_eq!(Baseline::from_json(&json).unwrap(), baseline);
+        assert!(Baseline::from_json(&Json::Null).is_err());
+
+        let measurement = baseline.find(3, 2, "input1.txt").unwrap();
+        assert_eq!(measurement.throughput(), 70_000.0);
+
+        let faster = Measurement {
+            solve: stats(100),
+            ..measurement.clone()
+        };
+        assert_eq!(faster.change_from(measurement), -20.0);
+        assert!(baseline.find(3, 1, "input1.txt").is_none());
+    }
+}
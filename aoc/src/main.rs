@@ -0,0 +1,674 @@
+use std::{
+    env, fs,
+    io::{self, BufReader},
+    process, thread,
+    time::Duration,
+};
+
+use common::{Answer, Format, Input, Json, Report, StderrLog, Verbosity, Verdict};
+
+mod bench;
+mod repl;
+mod solutions;
+mod watch;
+
+use bench::{Baseline, Measurement};
+use solutions::{
+    input_path, load_answers, measure, registered_input, registered_name, report, save_answers,
+    DAYS,
+};
+use watch::Watcher;
+
+const DEFAULT_ITERATIONS: usize = 10;
+const WATCH_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Command {
+    Run {
+        day: u32,
+        part: u32,
+        input: Option<String>,
+        record: bool,
+        watch: bool,
+        format: Format,
+        verbosity: Verbosity,
+    },
+    Check {
+        verbosity: Verbosity,
+    },
+    Bench {
+        day: Option<u32>,
+        part: Option<u32>,
+        iterations: usize,
+        baseline: Option<String>,
+        save: Option<String>,
+    },
+    Repl {
+        day: u32,
+        input: Option<String>,
+    },
+    Help,
+}
+
+fn print_usage() {
+    println!(
+        "usage: aoc run <day> <part> [input] [--record | --watch] [--format json|csv|text] [-v|-q]"
+    );
+    println!("       aoc check [-v|-q]");
+    println!(
+        "       aoc bench [<day> [<part>]] [--iterations <n>] [--baseline <file>] [--save <file>]"
+    );
+    println!("       aoc repl <day> [input]\n");
+    println!("  run <day> <part> [input]    solve one part of a day with its puzzle input");
+    println!("      --record                register the answer as verified for this input");
+    println!("      --watch                 solve again whenever the input file changes");
+    println!("      --format json|csv|text  print the answer with the data it was computed from");
+    println!(
+        "      -v, --verbose           also print per-record diagnostics of the parse to stderr"
+    );
+    println!("      -q, --quiet             print no diagnostics");
+    println!("  check [-v|-q]               solve every registered answer and compare");
+    println!("  bench [<day> [<part>]]      time parsing and solving, every day by default");
+    println!(
+        "      --iterations <n>        runs per part, {} by default",
+        DEFAULT_ITERATIONS
+    );
+    println!("      --baseline <file>       compare medians with a saved JSON baseline");
+    println!("      --save <file>           save the timings as a JSON baseline");
+    println!("  repl <day> [input]          load an input once and explore it, type 'help'");
+    println!("\ninput is a file path or '-' for stdin; without it the day-NN.txt file of");
+    println!(
+        "${} is used when set, else the input committed with the day",
+        common::INPUTS_DIR_VAR
+    );
+    println!("\navailable days: {:?}, parts: 1 and 2", DAYS);
+}
+
+fn parse_number(value: Option<&String>, name: &str) -> Result<u32, String> {
+    match value {
+        Some(value) => value
+            .parse()
+            .map_err(|_| format!("invalid {} '{}'", name, value)),
+        None => Err(format!("missing {}", name)),
+    }
+}
+
+fn parse_args(args: &[String]) -> Result<Command, String> {
+    let mut args_iter = args.iter();
+
+    let command = match args_iter.next().map(|arg| arg.as_str()) {
+        Some("run") => {
+            let day = parse_number(args_iter.next(), "day")?;
+            let part = parse_number(args_iter.next(), "part")?;
+            let mut input: Option<String> = None;
+            let mut record = false;
+            let mut watch = false;
+            let mut format: Option<Format> = None;
+            let mut verbosity = Verbosity::default();
+
+            while let Some(arg) = args_iter.next() {
+                if let Some(level) = Verbosity::from_flag(arg) {
+                    verbosity = level;
+                    continue;
+                }
+
+                match arg.as_str() {
+                    "--record" => record = true,
+                    "--watch" => watch = true,
+                    "--format" => format = Some(args_iter.next().ok_or("missing format")?.parse()?),
+                    path if (path == "-" || !path.starts_with('-')) && input.is_none() => {
+                        input = Some(path.to_string())
+                    }
+                    _ => return Err(format!("unexpected argument '{}'", arg)),
+                }
+            }
+
+            if record && watch {
+                return Err("'--record' and '--watch' cannot be used together".to_string());
+            }
+
+            Command::Run {
+                day,
+                part,
+                input,
+                record,
+                watch,
+                format: format.unwrap_or_default(),
+                verbosity,
+            }
+        }
+        Some("check") => {
+            let mut verbosity = Verbosity::default();
+
+            for arg in args_iter.by_ref() {
+                verbosity = Verbosity::from_flag(arg)
+                    .ok_or_else(|| format!("unexpected argument '{}'", arg))?;
+            }
+
+            Command::Check { verbosity }
+        }
+        Some("bench") => {
+            let mut day: Option<u32> = None;
+            let mut part: Option<u32> = None;
+            let mut iterations = DEFAULT_ITERATIONS;
+            let mut baseline: Option<String> = None;
+            let mut save: Option<String> = None;
+
+            while let Some(arg) = args_iter.next() {
+                match arg.as_str() {
+                    "--iterations" => {
+                        iterations = match parse_number(args_iter.next(), "iterations")? {
+                            0 => return Err("iterations must be at least 1".to_string()),
+                            iterations => iterations as usize,
+                        }
+                    }
+                    "--baseline" => {
+                        baseline = Some(args_iter.next().ok_or("missing baseline file")?.clone())
+                    }
+                    "--save" => save = Some(args_iter.next().ok_or("missing save file")?.clone()),
+                    _ if day.is_none() => day = Some(parse_number(Some(arg), "day")?),
+                    _ if part.is_none() => part = Some(parse_number(Some(arg), "part")?),
+                    _ => return Err(format!("unexpected argument '{}'", arg)),
+                }
+            }
+
+            Command::Bench {
+                day,
+                part,
+                iterations,
+                baseline,
+                save,
+            }
+        }
+        Some("repl") => {
+            let day = parse_number(args_iter.next(), "day")?;
+
+            match args_iter.next() {
+                Some(path) if path == "-" || path.starts_with('-') => {
+                    return Err(format!(
+                        "unexpected argument '{}', the repl reads commands from stdin",
+                        path
+                    ))
+                }
+                input => Command::Repl {
+                    day,
+                    input: input.cloned(),
+                },
+            }
+        }
+        Some("--help") | Some("-h") | None => Command::Help,
+        Some(command) => return Err(format!("unknown command '{}'", command)),
+    };
+
+    if let Some(arg) = args_iter.next() {
+        return Err(format!("unexpected argument '{}'", arg));
+    }
+
+    Ok(command)
+}
+
+/// Solves one part, showing the diagnostics of the parse that `verbosity` selects.
+fn run(day: u32, part: u32, input: &Input, verbosity: Verbosity) -> Result<Report, String> {
+    let content = input.read().map_err(|err| err.to_string())?;
+
+    report(day, part, &content, &mut StderrLog::new(verbosity)).map_err(|err| err.to_string())
+}
+
+/// Solves one part and compares it with the registry, recording it when asked.
+fn run_and_verify(
+    day: u32,
+    part: u32,
+    input: Option<&str>,
+    record: bool,
+    format: Format,
+    verbosity: Verbosity,
+) -> Result<(), String> {
+    let input = Input::locate(input, day, input_path(day));
+    let report = run(day, part, &input, verbosity)?;
+    let mut answers = load_answers()?;
+
+    let verdict = answers.check(day, part, registered_name(day, &input), &report.answer);
+    match format {
+        Format::Text => println!("day {} part {}: {} ({})", day, part, report.answer, verdict),
+        format => print!("{}", report.render(format)),
+    }
+
+    if record {
+        let name = registered_name(day, &input).ok_or_else(|| {
+            format!(
+                "cannot record an answer for input {}: only the committed inputs and files in {} can be registered",
+                input,
+                common::INPUTS_DIR_VAR
+            )
+        })?;
+
+        answers.record(Answer {
+            day,
+            part,
+            input: name.to_string(),
+            value: report.answer,
+        })?;
+        save_answers(&answers)?;
+        println!("recorded as the answer for {}", input);
+    } else if let Verdict::Fail { .. } = verdict {
+        return Err("answer does not match the registered one".to_string());
+    }
+
+    Ok(())
+}
+
+/// Solves one part every time its input file changes, printing how the
+/// answer and its rendering in `format` moved since the previous run. Never
+/// returns on success.
+fn run_on_change(
+    day: u32,
+    part: u32,
+    input: Option<&str>,
+    format: Format,
+    verbosity: Verbosity,
+) -> Result<(), String> {
+    let input = Input::locate(input, day, input_path(day));
+    let mut watcher = match &input {
+        Input::File(path) => Watcher::new(path),
+        Input::Stdin => return Err("cannot watch stdin, pass an input file".to_string()),
+    };
+    let answers = load_answers()?;
+    let mut previous: Option<Report> = None;
+
+    println!("watching {}, press Ctrl-C to stop", input);
+    loop {
+        match watcher.poll() {
+            Ok(false) => {}
+            // Editors often save by replacing the file, leaving it missing for a moment.
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => eprintln!("error: cannot watch {}: {}", input, err),
+            Ok(true) => match run(day, part, &input, verbosity) {
+                Ok(report) => {
+                    let verdict =
+                        answers.check(day, part, registered_name(day, &input), &report.answer);
+
+                    match &previous {
+                        Some(previous) => print!("{}", watch::compare(previous, &report, format)),
+                        None if format != Format::Text => print!("{}", report.render(format)),
+                        None => {}
+                    }
+                    if format == Format::Text {
+                        println!("day {} part {}: {} ({})", day, part, report.answer, verdict);
+                    }
+                    previous = Some(report);
+                }
+                Err(err) => eprintln!("error: day {} part {}: {}", day, part, err),
+            },
+        }
+
+        thread::sleep(WATCH_INTERVAL);
+    }
+}
+
+/// Solves every registered answer whose input is available, returning the failures.
+fn check(verbosity: Verbosity) -> Result<usize, String> {
+    let answers = load_answers()?;
+    let mut failures = 0;
+
+    for answer in answers.entries() {
+        let input = registered_input(answer.day, &answer.input);
+        let label = format!("day {} part {} {}", answer.day, answer.part, answer.input);
+
+        if let Input::File(path) = &input {
+            if !path.exists() {
+                println!("{}: skipped, input not found", label);
+                continue;
+            }
+        }
+
+        match run(answer.day, answer.part, &input, verbosity) {
+            Ok(report) => {
+                let verdict = answers.check(answer.day, answer.part, input.name(), &report.answer);
+                if verdict != Verdict::Pass {
+                    failures += 1;
+                }
+                println!("{}: {} ({})", label, report.answer, verdict);
+            }
+            Err(err) => {
+                failures += 1;
+                println!("{}: error, {}", label, err);
+            }
+        }
+    }
+
+    Ok(failures)
+}
+
+/// Parses the input of `day` once and answers commands typed on stdin.
+fn repl(day: u32, input: Option<&str>) -> Result<(), String> {
+    let input = Input::locate(input, day, input_path(day));
+    let content = input.read().map_err(|err| err.to_string())?;
+    let session = repl::session(day, &content)?;
+
+    println!(
+        "day {} loaded from {}, type 'help' for commands",
+        day, input
+    );
+    repl::run(
+        day,
+        session.as_ref(),
+        BufReader::new(io::stdin()),
+        &mut io::stdout(),
+    )
+    .map_err(|err| err.to_string())
+}
+
+fn load_baseline(path: &str) -> Result<Baseline, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|err| format!("cannot read baseline '{}': {}", path, err))?;
+    let json: Json = content
+        .parse()
+        .map_err(|err| format!("invalid baseline '{}': {}", path, err))?;
+
+    Baseline::from_json(&json)
+}
+
+/// Times the selected days and parts on their inputs, comparing with and
+/// saving to baselines when asked.
+fn bench(
+    day: Option<u32>,
+    part: Option<u32>,
+    iterations: usize,
+    baseline: Option<&str>,
+    save: Option<&str>,
+) -> Result<(), String> {
+    let baseline = baseline.map(load_baseline).transpose()?;
+    let days: Vec<u32> = match day {
+        Some(day) => vec![day],
+        None => DAYS.to_vec(),
+    };
+    let parts: Vec<u32> = match part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+    let mut measurements = Vec::new();
+
+    for day in days {
+        let input = Input::locate(None, day, input_path(day));
+        let content = input.read().map_err(|err| err.to_string())?;
+
+        for part in &parts {
+            let (parse, solve) = measure(day, *part, &content, iterations)
+                .map_err(|err| format!("day {} part {}: {}", day, part, err))?;
+            let measurement = Measurement {
+                day,
+                part: *part,
+                input: input.name().unwrap_or("stdin").to_string(),
+                lines: content.lines().count(),
+                parse,
+                solve,
+            };
+
+            println!("{}", measurement);
+            if let Some(before) = baseline.as_ref().and_then(|baseline| {
+                baseline.find(measurement.day, measurement.part, &measurement.input)
+            }) {
+                println!(
+                    "  baseline median {:.3?}, now {:.3?} ({:+.1}%)",
+                    before.median(),
+                    measurement.median(),
+                    measurement.change_from(before)
+                );
+            }
+
+            measurements.push(measurement);
+        }
+    }
+
+    if let Some(path) = save {
+        let baseline = Baseline {
+            iterations,
+            measurements,
+        };
+
+        fs::write(path, format!("{:#}\n", baseline.to_json()))
+            .map_err(|err| format!("cannot write baseline '{}': {}", path, err))?;
+        println!("baseline saved to '{}'", path);
+    }
+
+    Ok(())
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let command = match parse_args(&args) {
+        Ok(command) => command,
+        Err(err) => {
+            eprintln!("error: {}\n", err);
+            print_usage();
+            process::exit(1);
+        }
+    };
+
+    match command {
+        Command::Help => print_usage(),
+        Command::Run {
+            day,
+            part,
+            input,
+            record,
+            watch,
+            format,
+            verbosity,
+        } => {
+            let result = match watch {
+                true => run_on_change(day, part, input.as_deref(), format, verbosity),
+                false => run_and_verify(day, part, input.as_deref(), record, format, verbosity),
+            };
+
+            if let Err(err) = result {
+                eprintln!("error: day {} part {}: {}", day, part, err);
+                process::exit(1);
+            }
+        }
+        Command::Bench {
+            day,
+            part,
+            iterations,
+            baseline,
+            save,
+        } => {
+            if let Err(err) = bench(day, part, iterations, baseline.as_deref(), save.as_deref()) {
+                eprintln!("error: {}", err);
+                process::exit(1);
+            }
+        }
+        Command::Repl { day, input } => {
+            if let Err(err) = repl(day, input.as_deref()) {
+                eprintln!("error: day {}: {}", day, err);
+                process::exit(1);
+            }
+        }
+        Command::Check { verbosity } => match check(verbosity) {
+            Ok(0) => {}
+            Ok(failures) => {
+                eprintln!("error: {} registered answers failed", failures);
+                process::exit(1);
+            }
+            Err(err) => {
+                eprintln!("error: {}", err);
+                process::exit(1);
+            }
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(
+            parse_args(&args(&["run", "3", "2"])),
+            Ok(Command::Run {
+                day: 3,
+                part: 2,
+                input: None,
+                record: false,
+                watch: false,
+                format: Format::Text,
+                verbosity: Verbosity::Normal,
+            })
+        );
+        assert_eq!(
+            parse_args(&args(&["run", "1", "1", "-"])),
+            Ok(Command::Run {
+                day: 1,
+                part: 1,
+                input: Some("-".to_string()),
+                record: false,
+                watch: false,
+                format: Format::Text,
+                verbosity: Verbosity::Normal,
+            })
+        );
+        assert_eq!(
+            parse_args(&args(&["run", "3", "1", "--record", "input1.txt"])),
+            Ok(Command::Run {
+                day: 3,
+                part: 1,
+                input: Some("input1.txt".to_string()),
+                record: true,
+                watch: false,
+                format: Format::Text,
+                verbosity: Verbosity::Normal,
+            })
+        );
+        assert_eq!(
+            parse_args(&args(&["run", "2", "1", "--format", "csv"])),
+            Ok(Command::Run {
+                day: 2,
+                part: 1,
+                input: None,
+                record: false,
+                watch: false,
+                format: Format::Csv,
+                verbosity: Verbosity::Normal,
+            })
+        );
+        assert_eq!(
+            parse_args(&args(&["run", "1", "2", "test_input.txt", "--watch"])),
+            Ok(Command::Run {
+                day: 1,
+                part: 2,
+                input: Some("test_input.txt".to_string()),
+                record: false,
+                watch: true,
+                format: Format::Text,
+                verbosity: Verbosity::Normal,
+            })
+        );
+        assert!(parse_args(&args(&["run", "1", "2", "--watch", "--record"])).is_err());
+        assert!(parse_args(&args(&["run", "2", "1", "--format"])).is_err());
+        assert!(parse_args(&args(&["run", "2", "1", "--format", "xml"])).is_err());
+        assert_eq!(
+            parse_args(&args(&["run", "3", "1", "--verbose", "input1.txt"])),
+            Ok(Command::Run {
+                day: 3,
+                part: 1,
+                input: Some("input1.txt".to_string()),
+                record: false,
+                watch: false,
+                format: Format::Text,
+                verbosity: Verbosity::Verbose,
+            })
+        );
+        assert_eq!(
+            parse_args(&args(&["check"])),
+            Ok(Command::Check {
+                verbosity: Verbosity::Normal
+            })
+        );
+        assert_eq!(
+            parse_args(&args(&["check", "-q"])),
+            Ok(Command::Check {
+                verbosity: Verbosity::Quiet
+            })
+        );
+        assert_eq!(parse_args(&[]), Ok(Command::Help));
+        assert_eq!(parse_args(&args(&["-h"])), Ok(Command::Help));
+
+        assert!(parse_args(&args(&["run", "3"])).is_err());
+        assert!(parse_args(&args(&["run", "three", "1"])).is_err());
+        assert!(parse_args(&args(&["run", "3", "1", "a.txt", "b.txt"])).is_err());
+        assert!(parse_args(&args(&["run", "3", "1", "--loud"])).is_err());
+        assert!(parse_args(&args(&["check", "3"])).is_err());
+        assert!(parse_args(&args(&["solve"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_bench_args() {
+        assert_eq!(
+            parse_args(&args(&["bench"])),
+            Ok(Command::Bench {
+                day: None,
+                part: None,
+                iterations: DEFAULT_ITERATIONS,
+                baseline: None,
+                save: None,
+            })
+        );
+        assert_eq!(
+            parse_args(&args(&[
+                "bench",
+                "3",
+                "--iterations",
+                "50",
+                "2",
+                "--baseline",
+                "before.json",
+                "--save",
+                "after.json"
+            ])),
+            Ok(Command::Bench {
+                day: Some(3),
+                part: Some(2),
+                iterations: 50,
+                baseline: Some("before.json".to_string()),
+                save: Some("after.json".to_string()),
+            })
+        );
+
+        assert!(parse_args(&args(&["bench", "--iterations"])).is_err());
+        assert!(parse_args(&args(&["bench", "--iterations", "many"])).is_err());
+        assert_eq!(
+            parse_args(&args(&["bench", "--iterations", "0"])),
+            Err("iterations must be at least 1".to_string())
+        );
+        assert!(parse_args(&args(&["bench", "3", "2", "1"])).is_err());
+        assert!(parse_args(&args(&["bench", "--save"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_repl_args() {
+        assert_eq!(
+            parse_args(&args(&["repl", "2"])),
+            Ok(Command::Repl {
+                day: 2,
+                input: None,
+            })
+        );
+        assert_eq!(
+            parse_args(&args(&["repl", "3", "test_input.txt"])),
+            Ok(Command::Repl {
+                day: 3,
+                input: Some("test_input.txt".to_string()),
+            })
+        );
+
+        assert!(parse_args(&args(&["repl"])).is_err());
+        assert!(parse_args(&args(&["repl", "1", "-"])).is_err());
+        assert!(parse_args(&args(&["repl", "1", "a.txt", "b.txt"])).is_err());
+    }
+}
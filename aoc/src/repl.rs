@@ -0,0 +1,417 @@
+use std::{
+    io::{self, BufRead, Write},
+    str::FromStr,
+};
+
+use common::Solution;
+use day01::{Calibration, Digit, Line, DIGITS, NUMERALS};
+use day02::{BagReport, Cube, Games, Subset};
+use day03::{Connectivity, Element, Position, RenderStyle, Renderer, Schema};
+
+/// A day's input parsed once, answering the commands typed at the prompt.
+pub trait Session {
+    /// The day's own commands, one per line.
+    fn help(&self) -> &'static str;
+
+    fn answer(&self, part: u32) -> Result<String, String>;
+
+    fn command(&self, name: &str, args: &[&str]) -> Result<String, String>;
+}
+
+/// Parses `content` as the input of `day`.
+pub fn session(day: u32, content: &str) -> Result<Box<dyn Session>, String> {
+    let parse_error = |err: &dyn std::error::Error| format!("cannot parse input: {}", err);
+
+    match day {
+        1 => Calibration::parse(content)
+            .map(|calibration| Box::new(CalibrationSession { calibration }) as Box<dyn Session>)
+            .map_err(|err| parse_error(&err)),
+        2 => Games::parse(content)
+            .map(|games| Box::new(GamesSession { games }) as Box<dyn Session>)
+            .map_err(|err| parse_error(&err)),
+        3 => <Schema as Solution>::parse(content)
+            .map(|schema| Box::new(SchemaSession { schema }) as Box<dyn Session>)
+            .map_err(|err| parse_error(&err)),
+        _ => Err(format!("no solution for day {}", day)),
+    }
+}
+
+fn argument<T: FromStr>(args: &[&str], index: usize, name: &str) -> Result<T, String> {
+    match args.get(index) {
+        Some(value) => value
+            .parse()
+            .map_err(|_| format!("invalid {} '{}'", name, value)),
+        None => Err(format!("missing {}", name)),
+    }
+}
+
+fn answer<S: Solution>(solution: &S, part: u32) -> Result<String, String> {
+    match part {
+        1 => Ok(solution.part_one().to_string()),
+        2 => Ok(solution.part_two().to_string()),
+        _ => Err(format!("no part {}, parts are 1 and 2", part)),
+    }
+}
+
+fn unknown(name: &str) -> Result<String, String> {
+    Err(format!("unknown command '{}', type 'help'", name))
+}
+
+/// Reads commands from `input` until it ends or `quit` is typed, writing
+/// answers and errors to `output`.
+pub fn run(
+    day: u32,
+    session: &dyn Session,
+    input: impl BufRead,
+    output: &mut impl Write,
+) -> io::Result<()> {
+    write!(output, "day {}> ", day)?;
+    output.flush()?;
+
+    for line in input.lines() {
+        let line = line?;
+        let words: Vec<&str> = line.split_whitespace().collect();
+
+        let reply = match words.split_first() {
+            None => Ok(String::new()),
+            Some((&"quit", _)) | Some((&"exit", _)) => break,
+            Some((&"help", _)) => Ok(format!(
+                "part <1|2>          answer a part of the puzzle\n{}\nhelp                show this help\nquit                leave",
+                session.help()
+            )),
+            Some((&"part", args)) => {
+                argument(args, 0, "part").and_then(|part| session.answer(part))
+            }
+            Some((name, args)) => session.command(name, args),
+        };
+
+        match reply {
+            Ok(reply) if reply.is_empty() => {}
+            Ok(reply) => writeln!(output, "{}", reply)?,
+            Err(err) => writeln!(output, "error: {}", err)?,
+        }
+
+        write!(output, "day {}> ", day)?;
+        output.flush()?;
+    }
+
+    writeln!(output)
+}
+
+struct CalibrationSession {
+    calibration: Calibration,
+}
+
+fn digit_label(digit: &Digit) -> String {
+    match digit {
+        Digit::Number(spelling) => format!("{} ({})", spelling, digit.value()),
+        Digit::Empty | Digit::NaN => "none".to_string(),
+    }
+}
+
+impl Session for CalibrationSession {
+    fn help(&self) -> &'static str {
+        "line <n>            explain the calibration value of line n, from 1"
+    }
+
+    fn answer(&self, part: u32) -> Result<String, String> {
+        answer(&self.calibration, part)
+    }
+
+    fn command(&self, name: &str, args: &[&str]) -> Result<String, String> {
+        if name != "line" {
+            return unknown(name);
+        }
+
+        let number: usize = argument(args, 0, "line number")?;
+        let text = number
+            .checked_sub(1)
+            .and_then(|index| self.calibration.lines.get(index))
+            .ok_or_else(|| {
+                format!(
+                    "no line {}, lines go from 1 to {}",
+                    number,
+                    self.calibration.lines.len()
+                )
+            })?;
+
+        let explain = |label: &str, spellings| {
+            let line = Line::parse_with(text, spellings);
+            format!(
+                "  {}: first {}, last {} -> {}",
+                label,
+                digit_label(&line.head),
+                digit_label(&line.tail),
+                line.head.value() * 10 + line.tail.value()
+            )
+        };
+
+        Ok(format!(
+            "line {}: {:?}\n{}\n{}",
+            number,
+            text,
+            explain("part 1, digits", &NUMERALS),
+            explain("part 2, digits and words", &DIGITS)
+        ))
+    }
+}
+
+struct GamesSession {
+    games: Games,
+}
+
+fn subset_label(subset: &Subset) -> String {
+    format!(
+        "{} red, {} green, {} blue",
+        subset.red.count(),
+        subset.green.count(),
+        subset.blue.count()
+    )
+}
+
+impl Session for GamesSession {
+    fn help(&self) -> &'static str {
+        "game <id>           show a game, its smallest bag and whether the puzzle bag fits\n\
+         check <r> <g> <b>   list the games possible with a bag of r red, g green, b blue"
+    }
+
+    fn answer(&self, part: u32) -> Result<String, String> {
+        answer(&self.games, part)
+    }
+
+    fn command(&self, name: &str, args: &[&str]) -> Result<String, String> {
+        match name {
+            "game" => {
+                let id: i32 = argument(args, 0, "game id")?;
+                let game = self
+                    .games
+                    .0
+                    .iter()
+                    .find(|game| game.id == id)
+                    .ok_or_else(|| format!("no game {}", id))?;
+
+                let mut reply = format!("game {}: {} subsets", game.id, game.subsets.len());
+                for (position, subset) in game.subsets.iter().enumerate() {
+                    reply.push_str(&format!("\n  {}: {}", position + 1, subset_label(subset)));
+                }
+
+                let minimum = game.minimum_valid_combination();
+                let fits = BagReport::check(&day02::default_bag(), std::slice::from_ref(game));
+                reply.push_str(&format!(
+                    "\n  smallest bag: {} (power {})\n  possible with the puzzle bag: {}",
+                    subset_label(&minimum),
+                    minimum.pow(),
+                    if fits.valid_games.is_empty() {
+                        "no"
+                    } else {
+                        "yes"
+                    }
+                ));
+
+                Ok(reply)
+            }
+            "check" => {
+                let mut bag = Subset::new();
+                bag.add(Cube::Red(argument(args, 0, "red count")?));
+                bag.add(Cube::Green(argument(args, 1, "green count")?));
+                bag.add(Cube::Blue(argument(args, 2, "blue count")?));
+
+                let report = BagReport::check(&bag, &self.games.0);
+                Ok(format!(
+                    "{} of {} games possible with {}: {:?}\nsum of ids: {}",
+                    report.valid_games.len(),
+                    self.games.0.len(),
+                    subset_label(&bag),
+                    report.valid_games,
+                    report.id_sum
+                ))
+            }
+            _ => unknown(name),
+        }
+    }
+}
+
+struct SchemaSession {
+    schema: Schema,
+}
+
+impl SchemaSession {
+    fn describe(&self, element: &Element) -> String {
+        let position = element.position();
+        let kind = if !element.is_number() {
+            "symbol"
+        } else if self.schema.collides_with_symbol(element) {
+            "part"
+        } else {
+            "number"
+        };
+
+        format!(
+            "{} {} at ({}, {})",
+            kind,
+            element.value(),
+            position.x,
+            position.y
+        )
+    }
+}
+
+impl Session for SchemaSession {
+    fn help(&self) -> &'static str {
+        "at <x> <y>          show the element at a position, from 0, and its neighbours"
+    }
+
+    fn answer(&self, part: u32) -> Result<String, String> {
+        answer(&self.schema, part)
+    }
+
+    fn command(&self, name: &str, args: &[&str]) -> Result<String, String> {
+        if name != "at" {
+            return unknown(name);
+        }
+
+        let position = Position {
+            x: argument(args, 0, "x")?,
+            y: argument(args, 1, "y")?,
+        };
+        if !self.schema.contains(position) {
+            return Err(format!(
+                "({}, {}) is outside the {}x{} schema",
+                position.x,
+                position.y,
+                self.schema.width(),
+                self.schema.height()
+            ));
+        }
+
+        let (mut reply, neighbours) = match self.schema.get(position) {
+            Some(element) if element.is_number() || element.is_symbol() => {
+                (self.describe(element), self.schema.adjacent(element))
+            }
+            _ => {
+                let mut element_indexes: Vec<usize> = self
+                    .schema
+                    .index()
+                    .neighbours(position, Connectivity::Eight)
+                    .map(|(_, element_index)| *element_index)
+                    .collect();
+                element_indexes.sort_unstable();
+                element_indexes.dedup();
+
+                (
+                    format!("empty cell at ({}, {})", position.x, position.y),
+                    element_indexes
+                        .into_iter()
+                        .map(|element_index| &self.schema.elements()[element_index])
+                        .collect(),
+                )
+            }
+        };
+        let neighbours: Vec<&Element> = neighbours
+            .into_iter()
+            .filter(|neighbour| neighbour.is_number() || neighbour.is_symbol())
+            .collect();
+
+        if let Some(gear) = self
+            .schema
+            .gears()
+            .iter()
+            .find(|gear| *gear.symbol.position() == position)
+        {
+            reply.push_str(&format!(
+                ", gear {} * {} = {}",
+                gear.parts.0.value(),
+                gear.parts.1.value(),
+                gear.ratio()
+            ));
+        }
+
+        reply.push_str("\nneighbours:");
+        if neighbours.is_empty() {
+            reply.push_str(" none");
+        }
+        for neighbour in neighbours {
+            reply.push_str(&format!("\n  {}", self.describe(neighbour)));
+        }
+
+        let region = Renderer::new(&self.schema)
+            .style(RenderStyle::Brackets)
+            .around(position, 1)
+            .render();
+        reply.push_str(&format!("\n\n{}", region.trim_end()));
+
+        Ok(reply)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reply(day: u32, content: &str, commands: &str) -> String {
+        let session = session(day, content).unwrap();
+        let mut output = Vec::new();
+
+        run(day, session.as_ref(), commands.as_bytes(), &mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn test_run() {
+        let output = reply(
+            1,
+            "1abc2\ntreb7uchet",
+            "part 1\n\nbogus\npart 3\nquit\npart 2\n",
+        );
+
+        assert_eq!(
+            output,
+            "day 1> 89\nday 1> day 1> error: unknown command 'bogus', type 'help'\n\
+             day 1> error: no part 3, parts are 1 and 2\nday 1> \n"
+        );
+        assert!(reply(2, "Game 1: 1 red", "help\n").contains("check <r> <g> <b>"));
+    }
+
+    #[test]
+    fn test_calibration_session() {
+        let output = reply(1, "1abc2\ntwo1nine", "line 2\nline 3\nline x\n");
+
+        assert!(output.contains(
+            "line 2: \"two1nine\"\n  part 1, digits: first 1 (1), last 1 (1) -> 11\n  \
+             part 2, digits and words: first two (2), last nine (9) -> 29"
+        ));
+        assert!(output.contains("error: no line 3, lines go from 1 to 2"));
+        assert!(output.contains("error: invalid line number 'x'"));
+    }
+
+    #[test]
+    fn test_games_session() {
+        let content = "Game 1: 3 blue, 4 red; 1 red, 2 green\nGame 2: 20 red, 2 green";
+        let output = reply(2, content, "game 2\ncheck 12 13 14\ncheck 12\ngame 9\n");
+
+        assert!(output.contains(
+            "game 2: 1 subsets\n  1: 20 red, 2 green, 0 blue\n  \
+             smallest bag: 20 red, 2 green, 0 blue (power 0)\n  possible with the puzzle bag: no"
+        ));
+        assert!(output
+            .contains("1 of 2 games possible with 12 red, 13 green, 14 blue: [1]\nsum of ids: 1"));
+        assert!(output.contains("error: missing green count"));
+        assert!(output.contains("error: no game 9"));
+    }
+
+    #[test]
+    fn test_schema_session() {
+        let content = "467..114..\n...*......\n..35..633.";
+        let output = reply(3, content, "at 3 1\nat 6 0\nat 4 0\nat 20 0\n");
+
+        assert!(output.contains(
+            "symbol * at (3, 1), gear 467 * 35 = 16345\nneighbours:\n  \
+             part 467 at (0, 0)\n  part 35 at (2, 2)"
+        ));
+        assert!(output.contains("number 114 at (5, 0)\nneighbours: none"));
+        assert!(output.contains(
+            "empty cell at (4, 0)\nneighbours:\n  number 114 at (5, 0)\n  symbol * at (3, 1)"
+        ));
+        assert!(output.contains("error: (20, 0) is outside the 10x3 schema"));
+    }
+}
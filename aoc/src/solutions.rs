@@ -0,0 +1,180 @@
+use std::{
+    env,
+    error::Error,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use super::bench::{self, Stats};
+use common::{Answers, Input, Observer, Report, INPUTS_DIR_VAR};
+use day01::Calibration;
+use day02::Games;
+use day03::Schema;
+
+pub const DAYS: [u32; 3] = [1, 2, 3];
+
+/// The puzzle input of `day`, as committed in its package.
+pub fn input_path(day: u32) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join(format!("day-{:02}", day))
+        .join("src/bin/input1.txt")
+}
+
+/// The registry of verified answers, shared by every day.
+pub fn answers_path() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("answers.txt")
+}
+
+pub fn load_answers() -> Result<Answers, String> {
+    let path = answers_path();
+
+    match fs::read_to_string(&path) {
+        Ok(content) => content
+            .parse()
+            .map_err(|err| format!("invalid answers file '{}': {}", path.display(), err)),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+        Err(err) => Err(format!(
+            "cannot read answers file '{}': {}",
+            path.display(),
+            err
+        )),
+    }
+}
+
+pub fn save_answers(answers: &Answers) -> Result<(), String> {
+    let path = answers_path();
+
+    fs::write(&path, answers.to_string())
+        .map_err(|err| format!("cannot write answers file '{}': {}", path.display(), err))
+}
+
+/// The input registered as `name` for `day`: a file next to the committed
+/// input, else one in the inputs directory when it is set.
+pub fn registered_input(day: u32, name: &str) -> Input {
+    let committed = input_path(day).with_file_name(name);
+
+    match env::var_os(INPUTS_DIR_VAR) {
+        Some(inputs_dir) if !committed.exists() => {
+            Input::File(PathBuf::from(inputs_dir).join(name))
+        }
+        _ => Input::File(committed),
+    }
+}
+
+/// The name `input` is registered under for `day`: its file name, but only
+/// when `registered_input` resolves that name to this very file. Any other
+/// file is unknown to the registry, even if it shares a registered name.
+pub fn registered_name(day: u32, input: &Input) -> Option<&str> {
+    let name = input.name()?;
+    let (Input::File(path), Input::File(registered)) = (input, registered_input(day, name)) else {
+        return None;
+    };
+
+    match (fs::canonicalize(path), fs::canonicalize(registered)) {
+        (Ok(path), Ok(registered)) if path == registered => Some(name),
+        _ => None,
+    }
+}
+
+/// Solves `part` of `day` on `content`, with the data behind the answer,
+/// passing the diagnostics of the parse to `observer`.
+pub fn report(
+    day: u32,
+    part: u32,
+    content: &str,
+    observer: &mut dyn Observer,
+) -> Result<Report, Box<dyn Error>> {
+    match day {
+        1 => common::report_observed::<Calibration>(content, day, part, observer),
+        2 => common::report_observed::<Games>(content, day, part, observer),
+        3 => common::report_observed::<Schema>(content, day, part, observer),
+        _ => Err(format!("no solution for day {} part {}", day, part).into()),
+    }
+}
+
+/// Times the parse and solve phases of `part` of `day` on `content`.
+pub fn measure(
+    day: u32,
+    part: u32,
+    content: &str,
+    iterations: usize,
+) -> Result<(Stats, Stats), Box<dyn Error>> {
+    match day {
+        1 => bench::measure::<Calibration>(content, part, iterations),
+        2 => bench::measure::<Games>(content, part, iterations),
+        3 => bench::measure::<Schema>(content, part, iterations),
+        _ => Err(format!("no solution for day {} part {}", day, part).into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::{Silent, Verdict};
+
+    #[test]
+    fn test_registered_answers() {
+        let answers = load_answers().unwrap();
+        assert!(!answers.entries().is_empty());
+
+        for answer in answers.entries() {
+            let input = registered_input(answer.day, &answer.input);
+            let Ok(content) = input.read() else {
+                continue;
+            };
+
+            let report = report(answer.day, answer.part, &content, &mut Silent).unwrap();
+            assert_eq!(
+                answers.check(answer.day, answer.part, input.name(), &report.answer),
+                Verdict::Pass,
+                "day {} part {} on {}",
+                answer.day,
+                answer.part,
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_registered_name() {
+        let committed = Input::File(input_path(1));
+        assert_eq!(registered_name(1, &committed), Some("input1.txt"));
+        assert_eq!(registered_name(2, &committed), None);
+        assert_eq!(registered_name(1, &Input::Stdin), None);
+
+        let dir = env::temp_dir().join(format!("aoc-registered-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let other = dir.join("input1.txt");
+        fs::write(&other, "1abc2\n").unwrap();
+
+        assert_eq!(registered_name(1, &Input::File(other)), None);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_report_details() {
+        let content = fs::read_to_string(input_path(2).with_file_name("test_input.txt")).unwrap();
+        let report = report(2, 1, &content, &mut Silent).unwrap();
+
+        assert_eq!(report.answer, "8");
+        assert_eq!(report.details.name, "games");
+        assert_eq!(report.details.rows.len(), 5);
+    }
+
+    #[test]
+    fn test_report_errors() {
+        assert!(report(4, 1, "", &mut Silent).is_err());
+        assert!(report(1, 3, "", &mut Silent).is_err());
+        assert!(report(1, 1, "", &mut Silent).is_err());
+
+        let err = report(2, 1, "Game 1: 3 purple", &mut Silent).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 11: expected red, green or blue, found 'p'"
+        );
+    }
+}
@@ -0,0 +1,130 @@
+use std::{fs, io, path::PathBuf, time::SystemTime};
+
+use common::{Format, Report};
+
+/// Polls a file for changes to its modification time or its length.
+#[derive(Debug, Clone)]
+pub struct Watcher {
+    path: PathBuf,
+    stamp: Option<(SystemTime, u64)>,
+}
+
+impl Watcher {
+    pub fn new(path: impl Into<PathBuf>) -> Watcher {
+        Watcher {
+            path: path.into(),
+            stamp: None,
+        }
+    }
+
+    /// Whether the file changed since the last poll, always true on the first one.
+    pub fn poll(&mut self) -> io::Result<bool> {
+        let metadata = fs::metadata(&self.path)?;
+        let stamp = Some((metadata.modified()?, metadata.len()));
+
+        let changed = stamp != self.stamp;
+        self.stamp = stamp;
+        Ok(changed)
+    }
+}
+
+/// A report rendered in `format`, leaving out the answer line of the text
+/// rendering since `compare` prints the answer on its own.
+fn details(report: &Report, format: Format) -> String {
+    match format {
+        Format::Text => report
+            .to_text()
+            .lines()
+            .skip(1)
+            .collect::<Vec<&str>>()
+            .join("\n"),
+        format => report.render(format),
+    }
+}
+
+/// How `current` differs from the `previous` run: the answer, then the
+/// lines of its rendering in `format` that were removed or added.
+pub fn compare(previous: &Report, current: &Report, format: Format) -> String {
+    let answer = if previous.answer == current.answer {
+        format!("answer unchanged: {}\n", current.answer)
+    } else {
+        format!("answer: {} -> {}\n", previous.answer, current.answer)
+    };
+
+    answer + &common::format_diff(&details(previous, format), &details(current, format), 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, process};
+
+    use common::{Json, Table};
+
+    use super::*;
+
+    #[test]
+    fn test_watcher() {
+        let path = env::temp_dir().join(format!("aoc-watch-{}.txt", process::id()));
+        fs::write(&path, "1abc2\n").unwrap();
+
+        let mut watcher = Watcher::new(&path);
+        assert!(watcher.poll().unwrap());
+        assert!(!watcher.poll().unwrap());
+
+        fs::write(&path, "1abc2\npqr3stu8vwx\n").unwrap();
+        assert!(watcher.poll().unwrap());
+
+        fs::remove_file(&path).unwrap();
+        assert!(watcher.poll().is_err());
+    }
+
+    #[test]
+    fn test_compare() {
+        let report = |answer: &str, values: &[u32]| {
+            let mut details = Table::new("lines", &["line", "value"]);
+            for (line, value) in values.iter().enumerate() {
+                details.push(vec![Json::from(line + 1), Json::from(*value)]);
+            }
+
+            Report {
+                day: 1,
+                part: 1,
+                answer: answer.to_string(),
+                details,
+            }
+        };
+
+        assert_eq!(
+            compare(
+                &report("50", &[12, 38]),
+                &report("50", &[12, 38]),
+                Format::Text
+            ),
+            "answer unchanged: 50\n"
+        );
+        assert_eq!(
+            compare(
+                &report("50", &[12, 38]),
+                &report("127", &[12, 38, 77]),
+                Format::Text
+            ),
+            "answer: 50 -> 127\n  ...\n+      3     77\n"
+        );
+        assert_eq!(
+            compare(
+                &report("50", &[12, 38]),
+                &report("50", &[12, 40]),
+                Format::Csv
+            ),
+            "answer unchanged: 50\n  ...\n- 1,1,50,2,38\n+ 1,1,50,2,40\n"
+        );
+        assert_eq!(
+            compare(
+                &report("50", &[12, 38]),
+                &report("50", &[12, 38]),
+                Format::Json
+            ),
+            "answer unchanged: 50\n"
+        );
+    }
+}
@@ -0,0 +1,264 @@
+use std::{fmt, str::FromStr};
+
+use super::error::ParseError;
+use super::scanner::Scanner;
+
+/// A verified answer to one part of a day, for the input file named `input`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Answer {
+    pub day: u32,
+    pub part: u32,
+    pub input: String,
+    pub value: String,
+}
+
+impl Answer {
+    fn parse(scanner: &mut Scanner) -> Result<Answer, ParseError> {
+        let day = scanner.number()?;
+        scanner.spaces()?;
+        let part = scanner.number()?;
+        scanner.spaces()?;
+        let input = token(scanner, "an input name")?;
+        scanner.spaces()?;
+        let value = token(scanner, "an answer")?;
+        scanner.skip_spaces();
+
+        Ok(Answer {
+            day,
+            part,
+            input,
+            value,
+        })
+    }
+}
+
+fn token(scanner: &mut Scanner, expected: &str) -> Result<String, ParseError> {
+    let token = scanner.take_while(|character| !character.is_whitespace());
+
+    if token.is_empty() {
+        return Err(scanner.error(expected));
+    }
+
+    Ok(token)
+}
+
+/// How an answer compares with the registered one.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String },
+    Unknown,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "pass"),
+            Verdict::Fail { expected } => write!(f, "FAIL, expected {}", expected),
+            Verdict::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+/// Registry of verified answers, one `<day> <part> <input> <answer>` per line.
+///
+/// Blank lines and lines starting with `#` are ignored. Entries are kept
+/// sorted so that recording an answer produces a small diff of the file.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Answers {
+    entries: Vec<Answer>,
+}
+
+impl FromStr for Answers {
+    type Err = ParseError;
+
+    fn from_str(content: &str) -> Result<Self, Self::Err> {
+        let mut scanner = Scanner::new(content);
+        let mut answers = Answers::default();
+
+        while !scanner.is_done() {
+            scanner.skip_spaces();
+
+            match scanner.peek() {
+                Some('#') => {
+                    scanner.take_while(|character| character != '\n');
+                }
+                Some('\n') | Some('\r') | None => {}
+                Some(_) => answers.insert(Answer::parse(&mut scanner)?),
+            }
+
+            scanner.end_of_line()?;
+        }
+
+        Ok(answers)
+    }
+}
+
+impl fmt::Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "# <day> <part> <input> <answer>")?;
+
+        for answer in &self.entries {
+            writeln!(
+                f,
+                "{} {} {} {}",
+                answer.day, answer.part, answer.input, answer.value
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Answers {
+    pub fn entries(&self) -> &[Answer] {
+        &self.entries
+    }
+
+    pub fn get(&self, day: u32, part: u32, input: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|answer| answer.day == day && answer.part == part && answer.input == input)
+            .map(|answer| answer.value.as_str())
+    }
+
+    /// Compares `value` with the registered answer; `input` is `None` for
+    /// inputs without a name, such as stdin, which are never known.
+    pub fn check(&self, day: u32, part: u32, input: Option<&str>, value: &str) -> Verdict {
+        match input.and_then(|input| self.get(day, part, input)) {
+            Some(expected) if expected == value => Verdict::Pass,
+            Some(expected) => Verdict::Fail {
+                expected: expected.to_string(),
+            },
+            None => Verdict::Unknown,
+        }
+    }
+
+    /// Adds `answer`, replacing the one registered for the same day, part and input.
+    ///
+    /// Fields are separated by spaces in the file, so an input name or an
+    /// answer containing whitespace is rejected.
+    pub fn record(&mut self, answer: Answer) -> Result<(), String> {
+        for (field, value) in [("input name", &answer.input), ("answer", &answer.value)] {
+            if value.is_empty() || value.contains(char::is_whitespace) {
+                return Err(format!(
+                    "cannot record {} '{}': it must be a single word without whitespace",
+                    field, value
+                ));
+            }
+        }
+
+        self.insert(answer);
+        Ok(())
+    }
+
+    fn insert(&mut self, answer: Answer) {
+        let key = |entry: &Answer| (entry.day, entry.part, entry.input.clone());
+
+        match self
+            .entries
+            .binary_search_by(|entry| key(entry).cmp(&key(&answer)))
+        {
+            Ok(slot) => self.entries[slot] = answer,
+            Err(slot) => self.entries.insert(slot, answer),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_ANSWERS: &str =
+        "# <day> <part> <input> <answer>\n3 2 input1.txt 81939900\n\n1 1 test_input.txt 142  \n";
+
+    #[test]
+    fn test_parse_answers() {
+        let answers: Answers = TEST_ANSWERS.parse().unwrap();
+
+        assert_eq!(answers.entries().len(), 2);
+        assert_eq!(answers.get(1, 1, "test_input.txt"), Some("142"));
+        assert_eq!(answers.get(3, 2, "input1.txt"), Some("81939900"));
+        assert_eq!(answers.get(3, 1, "input1.txt"), None);
+
+        let err = "3 2 input1.txt\n".parse::<Answers>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 15: expected a space, found end of line"
+        );
+    }
+
+    #[test]
+    fn test_check() {
+        let answers: Answers = TEST_ANSWERS.parse().unwrap();
+
+        assert_eq!(
+            answers.check(1, 1, Some("test_input.txt"), "142"),
+            Verdict::Pass
+        );
+        assert_eq!(
+            answers.check(1, 1, Some("test_input.txt"), "143"),
+            Verdict::Fail {
+                expected: "142".to_string()
+            }
+        );
+        assert_eq!(
+            answers.check(1, 2, Some("test_input.txt"), "281"),
+            Verdict::Unknown
+        );
+        assert_eq!(answers.check(1, 1, None, "142"), Verdict::Unknown);
+    }
+
+    #[test]
+    fn test_record() {
+        let mut answers: Answers = TEST_ANSWERS.parse().unwrap();
+
+        answers
+            .record(Answer {
+                day: 1,
+                part: 2,
+                input: "test_input.txt".to_string(),
+                value: "281".to_string(),
+            })
+            .unwrap();
+        answers
+            .record(Answer {
+                day: 3,
+                part: 2,
+                input: "input1.txt".to_string(),
+                value: "1".to_string(),
+            })
+            .unwrap();
+
+        assert_eq!(
+            answers.to_string(),
+            "# <day> <part> <input> <answer>\n\
+             1 1 test_input.txt 142\n\
+             1 2 test_input.txt 281\n\
+             3 2 input1.txt 1\n"
+        );
+        assert_eq!(answers.to_string().parse::<Answers>().unwrap(), answers);
+
+        let err = answers
+            .record(Answer {
+                day: 1,
+                part: 2,
+                input: "my input.txt".to_string(),
+                value: "281".to_string(),
+            })
+            .unwrap_err();
+        assert_eq!(
+            err,
+            "cannot record input name 'my input.txt': it must be a single word without whitespace"
+        );
+        assert!(answers
+            .record(Answer {
+                day: 1,
+                part: 2,
+                input: "input.txt".to_string(),
+                value: String::new(),
+            })
+            .is_err());
+        assert_eq!(answers.to_string().parse::<Answers>().unwrap(), answers);
+    }
+}
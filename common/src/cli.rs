@@ -0,0 +1,115 @@
+use std::slice;
+
+use super::observer::Verbosity;
+use super::report::Format;
+
+/// Command line arguments shared by every day binary: the input file or `-`
+/// for stdin, `--format` and `-v`/`-q`.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct CommonArgs {
+    pub format: Format,
+    pub verbosity: Verbosity,
+    pub input: Option<String>,
+}
+
+impl CommonArgs {
+    /// Whether `--help` or `-h` is among `args`.
+    pub fn wants_help(args: &[String]) -> bool {
+        args.iter().any(|arg| arg == "--help" || arg == "-h")
+    }
+
+    /// Parses the shared arguments, handing every other flag to `extra`
+    /// together with the remaining arguments so that it can take its values.
+    /// `extra` returns whether it knew the flag.
+    pub fn parse<'a>(
+        args: &'a [String],
+        mut extra: impl FnMut(&'a str, &mut slice::Iter<'a, String>) -> Result<bool, String>,
+    ) -> Result<CommonArgs, String> {
+        let mut common = CommonArgs::default();
+        let mut args_iter = args.iter();
+
+        while let Some(arg) = args_iter.next() {
+            if let Some(level) = Verbosity::from_flag(arg) {
+                common.verbosity = level;
+                continue;
+            }
+
+            match arg.as_str() {
+                "--format" => match args_iter.next() {
+                    Some(value) => common.format = value.parse()?,
+                    None => return Err("missing value for '--format'".to_string()),
+                },
+                flag if extra(flag, &mut args_iter)? => {}
+                path if (path == "-" || !path.starts_with('-')) && common.input.is_none() => {
+                    common.input = Some(path.to_string())
+                }
+                _ => return Err(format!("unknown argument '{}'", arg)),
+            }
+        }
+
+        Ok(common)
+    }
+
+    /// `parse` for binaries without flags of their own.
+    pub fn parse_shared(args: &[String]) -> Result<CommonArgs, String> {
+        CommonArgs::parse(args, |_, _| Ok(false))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_shared() {
+        assert_eq!(
+            CommonArgs::parse_shared(&args(&["input.txt", "--format", "json"])),
+            Ok(CommonArgs {
+                format: Format::Json,
+                verbosity: Verbosity::Normal,
+                input: Some("input.txt".to_string()),
+            })
+        );
+        assert_eq!(
+            CommonArgs::parse_shared(&args(&["-q", "-v", "-"])),
+            Ok(CommonArgs {
+                format: Format::Text,
+                verbosity: Verbosity::Verbose,
+                input: Some("-".to_string()),
+            })
+        );
+        assert_eq!(CommonArgs::parse_shared(&[]), Ok(CommonArgs::default()));
+
+        assert!(CommonArgs::parse_shared(&args(&["--format"])).is_err());
+        assert!(CommonArgs::parse_shared(&args(&["--format", "yaml"])).is_err());
+        assert!(CommonArgs::parse_shared(&args(&["--loud"])).is_err());
+        assert!(CommonArgs::parse_shared(&args(&["a.txt", "b.txt"])).is_err());
+
+        assert!(CommonArgs::wants_help(&args(&["input.txt", "-h"])));
+        assert!(!CommonArgs::wants_help(&args(&["input.txt"])));
+    }
+
+    #[test]
+    fn test_parse_extra() {
+        let mut names = Vec::new();
+        let common = CommonArgs::parse(
+            &args(&["--name", "a", "-q", "input.txt", "--name", "b"]),
+            |flag, rest| match flag {
+                "--name" => {
+                    names.push(rest.next().ok_or("missing value for '--name'")?.clone());
+                    Ok(true)
+                }
+                _ => Ok(false),
+            },
+        )
+        .unwrap();
+
+        assert_eq!(names, vec!["a", "b"]);
+        assert_eq!(common.verbosity, Verbosity::Quiet);
+        assert_eq!(common.input, Some("input.txt".to_string()));
+    }
+}
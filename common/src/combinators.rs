@@ -0,0 +1,195 @@
+use std::str::FromStr;
+
+use super::error::ParseError;
+use super::scanner::Scanner;
+
+/// Small parsers built on top of the scanner.
+///
+/// Every combinator either consumes what it recognised or leaves the scanner
+/// where it was and returns a `ParseError` pointing at the offending character.
+impl Scanner {
+    pub fn error(&self, expected: &str) -> ParseError {
+        ParseError {
+            location: self.location(),
+            expected: expected.to_string(),
+            found: self.peek(),
+        }
+    }
+
+    /// Skips spaces and tabs, never line breaks.
+    pub fn skip_spaces(&mut self) {
+        self.take_while(|character| character == ' ' || character == '\t');
+    }
+
+    /// Like `skip_spaces`, but at least one space or tab is required.
+    pub fn spaces(&mut self) -> Result<(), ParseError> {
+        match self.peek() {
+            Some(' ') | Some('\t') => {
+                self.skip_spaces();
+                Ok(())
+            }
+            _ => Err(self.error("a space")),
+        }
+    }
+
+    /// An unsigned run of ASCII digits, parsed as `T`.
+    pub fn number<T: FromStr>(&mut self) -> Result<T, ParseError> {
+        let checkpoint = self.checkpoint();
+        let digits = self.take_while(|character| character.is_ascii_digit());
+
+        match digits.parse() {
+            Ok(number) => Ok(number),
+            Err(_) => {
+                self.rewind(checkpoint);
+                Err(self.error("a number"))
+            }
+        }
+    }
+
+    /// A non-empty run of ASCII letters.
+    pub fn word(&mut self) -> Result<String, ParseError> {
+        let word = self.take_while(|character| character.is_ascii_alphabetic());
+
+        if word.is_empty() {
+            return Err(self.error("a word"));
+        }
+
+        Ok(word)
+    }
+
+    /// Exactly the characters of `expected`.
+    pub fn literal(&mut self, expected: &str) -> Result<(), ParseError> {
+        let checkpoint = self.checkpoint();
+
+        for character in expected.chars() {
+            if self.consume_if(|next| next == character).is_none() {
+                self.rewind(checkpoint);
+                return Err(self.error(&format!("{:?}", expected)));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// One or more `item`s separated by `separator`, with optional spaces
+    /// around each separator.
+    pub fn separated_by<T>(
+        &mut self,
+        separator: &str,
+        mut item: impl FnMut(&mut Scanner) -> Result<T, ParseError>,
+    ) -> Result<Vec<T>, ParseError> {
+        let mut items = vec![item(self)?];
+
+        loop {
+            let checkpoint = self.checkpoint();
+            self.skip_spaces();
+
+            if self.literal(separator).is_err() {
+                self.rewind(checkpoint);
+                return Ok(items);
+            }
+
+            self.skip_spaces();
+            items.push(item(self)?);
+        }
+    }
+
+    /// A line break, or the end of the input.
+    pub fn end_of_line(&mut self) -> Result<(), ParseError> {
+        let checkpoint = self.checkpoint();
+        self.consume_if(|character| character == '\r');
+
+        match self.peek() {
+            Some('\n') => {
+                self.next();
+                Ok(())
+            }
+            None => Ok(()),
+            Some(_) => {
+                self.rewind(checkpoint);
+                Err(self.error("end of line"))
+            }
+        }
+    }
+
+    pub fn end(&self) -> Result<(), ParseError> {
+        match self.peek() {
+            Some(_) => Err(self.error("end of input")),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Location;
+    use super::*;
+
+    #[test]
+    fn test_number() {
+        let mut scanner = Scanner::new("467..x");
+
+        assert_eq!(scanner.number::<u32>(), Ok(467));
+        assert_eq!(scanner.literal(".."), Ok(()));
+
+        let error = scanner.number::<u32>().unwrap_err();
+        assert_eq!(error.location, Location { line: 1, column: 6 });
+        assert_eq!(error.found, Some('x'));
+        assert_eq!(scanner.cursor(), 5);
+
+        let mut scanner = Scanner::new("99999999999");
+        assert!(scanner.number::<u32>().is_err());
+        assert_eq!(scanner.cursor(), 0);
+        assert_eq!(scanner.number::<u64>(), Ok(99999999999));
+    }
+
+    #[test]
+    fn test_word_and_literal() {
+        let mut scanner = Scanner::new("Game 12: red");
+
+        assert_eq!(scanner.word(), Ok("Game".to_string()));
+        assert!(scanner.word().is_err());
+        assert_eq!(scanner.spaces(), Ok(()));
+        assert!(scanner.spaces().is_err());
+        assert_eq!(scanner.number::<i32>(), Ok(12));
+
+        let error = scanner.literal(": blue").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 8: expected \": blue\", found ':'"
+        );
+        assert_eq!(scanner.literal(": "), Ok(()));
+        assert_eq!(scanner.word(), Ok("red".to_string()));
+        assert_eq!(scanner.end(), Ok(()));
+    }
+
+    #[test]
+    fn test_separated_by() {
+        let mut scanner = Scanner::new("1, 2 ,3;4");
+
+        let numbers = scanner.separated_by(",", |scanner| scanner.number::<u32>());
+        assert_eq!(numbers, Ok(vec![1, 2, 3]));
+        assert_eq!(scanner.peek(), Some(';'));
+
+        let mut scanner = Scanner::new("1, x");
+        let error = scanner
+            .separated_by(",", |scanner| scanner.number::<u32>())
+            .unwrap_err();
+        assert_eq!(error.location, Location { line: 1, column: 4 });
+    }
+
+    #[test]
+    fn test_end_of_line() {
+        let mut scanner = Scanner::new("1\r\n2\n3 4");
+
+        for number in 1..=2 {
+            assert_eq!(scanner.number::<u32>(), Ok(number));
+            assert_eq!(scanner.end_of_line(), Ok(()));
+        }
+
+        assert_eq!(scanner.location(), Location { line: 3, column: 1 });
+        assert_eq!(scanner.number::<u32>(), Ok(3));
+        assert!(scanner.end_of_line().is_err());
+        assert!(scanner.end().is_err());
+    }
+}
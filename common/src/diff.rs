@@ -0,0 +1,129 @@
+use std::fmt;
+
+/// One line of a line-by-line comparison.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Change<'a> {
+    Same(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+impl Change<'_> {
+    pub fn is_same(&self) -> bool {
+        matches!(self, Change::Same(_))
+    }
+}
+
+impl fmt::Display for Change<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Change::Same(line) => write!(f, "  {}", line),
+            Change::Removed(line) => write!(f, "- {}", line),
+            Change::Added(line) => write!(f, "+ {}", line),
+        }
+    }
+}
+
+/// The lines of `before` and `after`, keeping their longest common
+/// subsequence and marking the others as removed or added.
+pub fn diff_lines<'a>(before: &'a str, after: &'a str) -> Vec<Change<'a>> {
+    let before: Vec<&str> = before.lines().collect();
+    let after: Vec<&str> = after.lines().collect();
+
+    // common[i][j] is the length of the longest common subsequence of
+    // before[i..] and after[j..].
+    let mut common = vec![vec![0usize; after.len() + 1]; before.len() + 1];
+    for i in (0..before.len()).rev() {
+        for j in (0..after.len()).rev() {
+            common[i][j] = if before[i] == after[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let mut changes = Vec::with_capacity(before.len().max(after.len()));
+    while i < before.len() && j < after.len() {
+        if before[i] == after[j] {
+            changes.push(Change::Same(before[i]));
+            i += 1;
+            j += 1;
+        } else if common[i + 1][j] >= common[i][j + 1] {
+            changes.push(Change::Removed(before[i]));
+            i += 1;
+        } else {
+            changes.push(Change::Added(after[j]));
+            j += 1;
+        }
+    }
+    changes.extend(before[i..].iter().map(|line| Change::Removed(line)));
+    changes.extend(after[j..].iter().map(|line| Change::Added(line)));
+
+    changes
+}
+
+/// The changed lines between `before` and `after` with `context` unchanged
+/// lines around them, or an empty string when they are equal.
+pub fn format_diff(before: &str, after: &str, context: usize) -> String {
+    let changes = diff_lines(before, after);
+    if changes.iter().all(Change::is_same) {
+        return String::new();
+    }
+
+    let shown: Vec<bool> = (0..changes.len())
+        .map(|position| {
+            let start = position.saturating_sub(context);
+            let end = (position + context + 1).min(changes.len());
+
+            changes[start..end].iter().any(|change| !change.is_same())
+        })
+        .collect();
+
+    let mut diff = String::new();
+    for (position, change) in changes.iter().enumerate() {
+        if shown[position] {
+            diff.push_str(&format!("{}\n", change));
+        } else if position == 0 || shown[position - 1] {
+            diff.push_str("  ...\n");
+        }
+    }
+
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_lines() {
+        assert_eq!(
+            diff_lines("a\nb\nc\nd", "a\nc\nx\nd\ne"),
+            vec![
+                Change::Same("a"),
+                Change::Removed("b"),
+                Change::Same("c"),
+                Change::Added("x"),
+                Change::Same("d"),
+                Change::Added("e"),
+            ]
+        );
+        assert_eq!(diff_lines("", "a"), vec![Change::Added("a")]);
+        assert!(diff_lines("a\nb", "a\nb").iter().all(Change::is_same));
+    }
+
+    #[test]
+    fn test_format_diff() {
+        let before = "1\n2\n3\n4\n5\n6\n7";
+        let after = "1\n2\n3\nfour\n5\n6\n7";
+
+        assert_eq!(
+            format_diff(before, after, 1),
+            "  ...\n  3\n- 4\n+ four\n  5\n  ...\n"
+        );
+        assert_eq!(format_diff(before, after, 0), "  ...\n- 4\n+ four\n  ...\n");
+        assert_eq!(format_diff(before, before, 3), "");
+    }
+}
@@ -0,0 +1,56 @@
+use std::{error::Error, fmt};
+
+use super::scanner::Location;
+
+/// What the scanner expected at `location` and the character it found instead.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ParseError {
+    pub location: Location,
+    pub expected: String,
+    pub found: Option<char>,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}, ",
+            self.location.line, self.location.column, self.expected
+        )?;
+
+        match self.found {
+            Some('\n') => write!(f, "found end of line"),
+            Some(character) => write!(f, "found {:?}", character),
+            None => write!(f, "found end of input"),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let error = ParseError {
+            location: Location { line: 3, column: 9 },
+            expected: "number".to_string(),
+            found: Some('x'),
+        };
+        assert_eq!(
+            error.to_string(),
+            "line 3, column 9: expected number, found 'x'"
+        );
+
+        let error = ParseError {
+            found: None,
+            ..error
+        };
+        assert_eq!(
+            error.to_string(),
+            "line 3, column 9: expected number, found end of input"
+        );
+    }
+}
@@ -0,0 +1,163 @@
+use std::{
+    env,
+    error::Error,
+    fmt, fs,
+    io::{self, Read},
+    path::PathBuf,
+};
+
+/// Directory holding one `day-NN.txt` input per day.
+pub const INPUTS_DIR_VAR: &str = "AOC_INPUTS_DIR";
+
+/// Where a puzzle input is read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Input {
+    File(PathBuf),
+    Stdin,
+}
+
+impl fmt::Display for Input {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Input::File(path) => write!(f, "'{}'", path.display()),
+            Input::Stdin => write!(f, "from stdin"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct InputError {
+    pub input: Input,
+    pub source: io::Error,
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "cannot read input {}: {}", self.input, self.source)?;
+
+        if self.source.kind() == io::ErrorKind::NotFound {
+            write!(
+                f,
+                " (pass an input file, '-' to read stdin, or set {} to a directory of day-NN.txt files)",
+                INPUTS_DIR_VAR
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Error for InputError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+impl Input {
+    /// `-` stands for stdin, anything else is a file path.
+    pub fn from_arg(arg: &str) -> Input {
+        match arg {
+            "-" => Input::Stdin,
+            path => Input::File(PathBuf::from(path)),
+        }
+    }
+
+    /// The input of `day`: `arg` when given, else its file in the directory
+    /// named by `AOC_INPUTS_DIR` when set, else `default`.
+    pub fn locate(arg: Option<&str>, day: u32, default: impl Into<PathBuf>) -> Input {
+        let inputs_dir = env::var_os(INPUTS_DIR_VAR).map(PathBuf::from);
+
+        Input::locate_in(arg, day, inputs_dir, default)
+    }
+
+    pub fn locate_in(
+        arg: Option<&str>,
+        day: u32,
+        inputs_dir: Option<PathBuf>,
+        default: impl Into<PathBuf>,
+    ) -> Input {
+        match (arg, inputs_dir) {
+            (Some(arg), _) => Input::from_arg(arg),
+            (None, Some(inputs_dir)) => Input::File(inputs_dir.join(format!("day-{:02}.txt", day))),
+            (None, None) => Input::File(default.into()),
+        }
+    }
+
+    /// The file name of the input, under which its answers are registered.
+    pub fn name(&self) -> Option<&str> {
+        match self {
+            Input::File(path) => path.file_name().and_then(|name| name.to_str()),
+            Input::Stdin => None,
+        }
+    }
+
+    pub fn read(&self) -> Result<String, InputError> {
+        match self {
+            Input::File(path) => fs::read_to_string(path),
+            Input::Stdin => {
+                let mut content = String::new();
+                io::stdin().read_to_string(&mut content).map(|_| content)
+            }
+        }
+        .map_err(|source| InputError {
+            input: self.clone(),
+            source,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locate() {
+        let default = "src/bin/input1.txt";
+
+        assert_eq!(
+            Input::locate_in(
+                Some("my_input.txt"),
+                3,
+                Some(PathBuf::from("inputs")),
+                default
+            ),
+            Input::File(PathBuf::from("my_input.txt"))
+        );
+        assert_eq!(Input::locate_in(Some("-"), 3, None, default), Input::Stdin);
+        assert_eq!(
+            Input::locate_in(None, 3, Some(PathBuf::from("inputs")), default),
+            Input::File(PathBuf::from("inputs/day-03.txt"))
+        );
+        assert_eq!(
+            Input::locate_in(None, 3, None, default),
+            Input::File(PathBuf::from(default))
+        );
+    }
+
+    #[test]
+    fn test_name() {
+        assert_eq!(
+            Input::from_arg("inputs/day-03.txt").name(),
+            Some("day-03.txt")
+        );
+        assert_eq!(Input::Stdin.name(), None);
+    }
+
+    #[test]
+    fn test_missing_input() {
+        let err = Input::from_arg("missing/day-42.txt").read().unwrap_err();
+
+        assert_eq!(err.source.kind(), io::ErrorKind::NotFound);
+        assert!(err
+            .to_string()
+            .starts_with("cannot read input 'missing/day-42.txt': "));
+        assert!(err.to_string().contains(INPUTS_DIR_VAR));
+    }
+
+    #[test]
+    fn test_read_file() {
+        let content = Input::from_arg("Cargo.toml").read().unwrap();
+
+        assert!(content.starts_with("[package]"));
+    }
+}
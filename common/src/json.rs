@@ -0,0 +1,388 @@
+use std::{fmt, str::FromStr};
+
+use super::error::ParseError;
+use super::scanner::Scanner;
+
+/// A JSON document, enough of it for baselines and reports.
+///
+/// Objects keep their keys in insertion order so that files written from a
+/// `Json` value diff cleanly. `{}` formats compactly, `{:#}` indents by two
+/// spaces.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    /// An object built from `(key, value)` pairs.
+    pub fn object<K: Into<String>>(entries: impl IntoIterator<Item = (K, Json)>) -> Json {
+        Json::Object(
+            entries
+                .into_iter()
+                .map(|(key, value)| (key.into(), value))
+                .collect(),
+        )
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Json::Object(entries) => entries
+                .iter()
+                .find(|(entry_key, _)| entry_key == key)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Json::Number(number) => Some(*number),
+            _ => None,
+        }
+    }
+
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            Json::Number(number) if *number >= 0.0 && number.fract() == 0.0 => Some(*number as u64),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Json::String(string) => Some(string),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Json]> {
+        match self {
+            Json::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    fn parse(scanner: &mut Scanner) -> Result<Json, ParseError> {
+        skip_whitespace(scanner);
+
+        let value = match scanner.peek() {
+            Some('n') => scanner.literal("null").map(|_| Json::Null)?,
+            Some('t') => scanner.literal("true").map(|_| Json::Bool(true))?,
+            Some('f') => scanner.literal("false").map(|_| Json::Bool(false))?,
+            Some('"') => Json::String(parse_string(scanner)?),
+            Some('[') => Json::Array(parse_sequence(scanner, '[', ']', Json::parse)?),
+            Some('{') => Json::Object(parse_sequence(scanner, '{', '}', |scanner| {
+                skip_whitespace(scanner);
+                let key = parse_string(scanner)?;
+                skip_whitespace(scanner);
+                scanner.literal(":")?;
+
+                Ok((key, Json::parse(scanner)?))
+            })?),
+            Some(character) if character == '-' || character.is_ascii_digit() => {
+                parse_number(scanner)?
+            }
+            _ => return Err(scanner.error("a JSON value")),
+        };
+
+        skip_whitespace(scanner);
+        Ok(value)
+    }
+}
+
+fn skip_whitespace(scanner: &mut Scanner) {
+    scanner.take_while(|character| matches!(character, ' ' | '\t' | '\n' | '\r'));
+}
+
+/// `item`s between `open` and `close`, separated by commas.
+fn parse_sequence<T>(
+    scanner: &mut Scanner,
+    open: char,
+    close: char,
+    mut item: impl FnMut(&mut Scanner) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    let mut items = Vec::new();
+
+    scanner.literal(&open.to_string())?;
+    skip_whitespace(scanner);
+    if scanner.consume_if(|character| character == close).is_some() {
+        return Ok(items);
+    }
+
+    loop {
+        items.push(item(scanner)?);
+
+        match scanner.peek() {
+            Some(',') => {
+                scanner.next();
+            }
+            Some(character) if character == close => {
+                scanner.next();
+                return Ok(items);
+            }
+            _ => return Err(scanner.error(&format!("',' or '{}'", close))),
+        }
+    }
+}
+
+fn parse_number(scanner: &mut Scanner) -> Result<Json, ParseError> {
+    let checkpoint = scanner.checkpoint();
+    let number = scanner.take_while(|character| {
+        character.is_ascii_digit() || matches!(character, '-' | '+' | '.' | 'e' | 'E')
+    });
+
+    match number.parse() {
+        Ok(number) => Ok(Json::Number(number)),
+        Err(_) => {
+            scanner.rewind(checkpoint);
+            Err(scanner.error("a number"))
+        }
+    }
+}
+
+fn parse_string(scanner: &mut Scanner) -> Result<String, ParseError> {
+    scanner.literal("\"")?;
+    let mut string = String::new();
+
+    loop {
+        match scanner.peek() {
+            Some('"') => {
+                scanner.next();
+                return Ok(string);
+            }
+            Some('\\') => {
+                scanner.next();
+                let escaped = match scanner.peek() {
+                    Some('"') => '"',
+                    Some('\\') => '\\',
+                    Some('/') => '/',
+                    Some('b') => '\u{8}',
+                    Some('f') => '\u{c}',
+                    Some('n') => '\n',
+                    Some('r') => '\r',
+                    Some('t') => '\t',
+                    Some('u') => {
+                        scanner.next();
+                        let digits: String = (0..4).filter_map(|_| scanner.next()).collect();
+
+                        match u32::from_str_radix(&digits, 16)
+                            .ok()
+                            .and_then(char::from_u32)
+                        {
+                            Some(character) => {
+                                string.push(character);
+                                continue;
+                            }
+                            None => return Err(scanner.error("a unicode escape")),
+                        }
+                    }
+                    _ => return Err(scanner.error("an escape sequence")),
+                };
+
+                scanner.next();
+                string.push(escaped);
+            }
+            Some(character) if character != '\n' => {
+                scanner.next();
+                string.push(character);
+            }
+            _ => return Err(scanner.error("'\"'")),
+        }
+    }
+}
+
+impl FromStr for Json {
+    type Err = ParseError;
+
+    fn from_str(content: &str) -> Result<Self, Self::Err> {
+        let mut scanner = Scanner::new(content);
+        let json = Json::parse(&mut scanner)?;
+
+        scanner.end()?;
+        Ok(json)
+    }
+}
+
+fn write_string(f: &mut fmt::Formatter<'_>, string: &str) -> fmt::Result {
+    write!(f, "\"")?;
+
+    for character in string.chars() {
+        match character {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\r' => write!(f, "\\r")?,
+            '\t' => write!(f, "\\t")?,
+            character if character.is_control() => write!(f, "\\u{:04x}", character as u32)?,
+            character => write!(f, "{}", character)?,
+        }
+    }
+
+    write!(f, "\"")
+}
+
+impl Json {
+    fn write(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+        let pretty = f.alternate();
+        let indent = |f: &mut fmt::Formatter<'_>, depth: usize| {
+            if pretty {
+                write!(f, "\n{:width$}", "", width = depth * 2)
+            } else {
+                Ok(())
+            }
+        };
+
+        match self {
+            Json::Null => write!(f, "null"),
+            Json::Bool(value) => write!(f, "{}", value),
+            Json::Number(number) if !number.is_finite() => write!(f, "null"),
+            Json::Number(number) if number.fract() == 0.0 && number.abs() < 1e15 => {
+                write!(f, "{}", *number as i64)
+            }
+            Json::Number(number) => write!(f, "{}", number),
+            Json::String(string) => write_string(f, string),
+            Json::Array(values) if values.is_empty() => write!(f, "[]"),
+            Json::Array(values) => {
+                write!(f, "[")?;
+                for (position, value) in values.iter().enumerate() {
+                    if position > 0 {
+                        write!(f, ",")?;
+                    }
+                    indent(f, depth + 1)?;
+                    value.write(f, depth + 1)?;
+                }
+                indent(f, depth)?;
+                write!(f, "]")
+            }
+            Json::Object(entries) if entries.is_empty() => write!(f, "{{}}"),
+            Json::Object(entries) => {
+                write!(f, "{{")?;
+                for (position, (key, value)) in entries.iter().enumerate() {
+                    if position > 0 {
+                        write!(f, ",")?;
+                    }
+                    indent(f, depth + 1)?;
+                    write_string(f, key)?;
+                    write!(f, "{}", if pretty { ": " } else { ":" })?;
+                    value.write(f, depth + 1)?;
+                }
+                indent(f, depth)?;
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+impl fmt::Display for Json {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write(f, 0)
+    }
+}
+
+impl From<bool> for Json {
+    fn from(value: bool) -> Self {
+        Json::Bool(value)
+    }
+}
+
+impl From<&str> for Json {
+    fn from(value: &str) -> Self {
+        Json::String(value.to_string())
+    }
+}
+
+impl From<String> for Json {
+    fn from(value: String) -> Self {
+        Json::String(value)
+    }
+}
+
+macro_rules! json_from_number {
+    ($($number:ty),*) => {
+        $(impl From<$number> for Json {
+            fn from(value: $number) -> Self {
+                Json::Number(value as f64)
+            }
+        })*
+    };
+}
+
+json_from_number!(u32, u64, usize, i64, f64);
+
+impl<T: Into<Json>> From<Vec<T>> for Json {
+    fn from(values: Vec<T>) -> Self {
+        Json::Array(values.into_iter().map(Into::into).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_json() {
+        let json: Json = r#" {"day": 3, "ok": true, "none": null,
+            "name": "a \"quoted\"\nline é", "times": [1.5, -2, 3e2], "empty": {}} "#
+            .parse()
+            .unwrap();
+
+        assert_eq!(json.get("day").and_then(Json::as_u64), Some(3));
+        assert_eq!(json.get("ok"), Some(&Json::Bool(true)));
+        assert_eq!(json.get("none"), Some(&Json::Null));
+        assert_eq!(
+            json.get("name").and_then(Json::as_str),
+            Some("a \"quoted\"\nline é")
+        );
+        assert_eq!(
+            json.get("times").and_then(Json::as_array),
+            Some(&[Json::Number(1.5), Json::Number(-2.0), Json::Number(300.0)][..])
+        );
+        assert_eq!(json.get("empty"), Some(&Json::Object(Vec::new())));
+        assert_eq!(json.get("missing"), None);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = "[1, 2".parse::<Json>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 6: expected ',' or ']', found end of input"
+        );
+
+        let err = "{\"a\" 1}".parse::<Json>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 6: expected \":\", found '1'"
+        );
+
+        assert!("[1] 2".parse::<Json>().is_err());
+        assert!("-".parse::<Json>().is_err());
+        assert!("\"open".parse::<Json>().is_err());
+    }
+
+    #[test]
+    fn test_display() {
+        let json = Json::object([
+            ("day", Json::from(1u32)),
+            ("median", Json::from(0.25)),
+            ("input", Json::from("tab\there")),
+            ("values", Json::from(vec![1u32, 2])),
+            ("empty", Json::Array(Vec::new())),
+        ]);
+
+        assert_eq!(
+            json.to_string(),
+            r#"{"day":1,"median":0.25,"input":"tab\there","values":[1,2],"empty":[]}"#
+        );
+        assert_eq!(
+            format!("{:#}", json),
+            "{\n  \"day\": 1,\n  \"median\": 0.25,\n  \"input\": \"tab\\there\",\n  \"values\": [\n    1,\n    2\n  ],\n  \"empty\": []\n}"
+        );
+        assert_eq!(json.to_string().parse::<Json>().unwrap(), json);
+    }
+}
@@ -0,0 +1,24 @@
+mod answers;
+mod cli;
+mod combinators;
+mod diff;
+mod error;
+mod input;
+mod json;
+mod observer;
+mod report;
+mod scanner;
+mod snapshot;
+mod solution;
+
+pub use answers::*;
+pub use cli::*;
+pub use diff::*;
+pub use error::*;
+pub use input::*;
+pub use json::*;
+pub use observer::*;
+pub use report::*;
+pub use scanner::*;
+pub use snapshot::*;
+pub use solution::*;
@@ -0,0 +1,125 @@
+use std::fmt;
+
+/// How much a diagnostic matters: `Info` for summaries, `Debug` for
+/// per-record details.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd)]
+pub enum Level {
+    Info,
+    Debug,
+}
+
+/// How many diagnostics a binary shows, set with `-q` and `-v`.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub enum Verbosity {
+    Quiet,
+    #[default]
+    Normal,
+    Verbose,
+}
+
+impl Verbosity {
+    /// The verbosity selected by a command line flag, if `arg` is one.
+    pub fn from_flag(arg: &str) -> Option<Verbosity> {
+        match arg {
+            "-q" | "--quiet" => Some(Verbosity::Quiet),
+            "-v" | "--verbose" => Some(Verbosity::Verbose),
+            _ => None,
+        }
+    }
+
+    pub fn shows(&self, level: Level) -> bool {
+        match self {
+            Verbosity::Quiet => false,
+            Verbosity::Normal => level == Level::Info,
+            Verbosity::Verbose => true,
+        }
+    }
+}
+
+/// Receives the diagnostics of library code, which never prints on its own.
+///
+/// Messages are passed as `fmt::Arguments` so that observers ignoring them
+/// never pay for the formatting.
+pub trait Observer {
+    fn event(&mut self, level: Level, message: fmt::Arguments<'_>);
+}
+
+/// Drops every event.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Silent;
+
+impl Observer for Silent {
+    fn event(&mut self, _level: Level, _message: fmt::Arguments<'_>) {}
+}
+
+/// Prints the events its verbosity shows to stderr, keeping stdout for results.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StderrLog {
+    pub verbosity: Verbosity,
+}
+
+impl StderrLog {
+    pub fn new(verbosity: Verbosity) -> StderrLog {
+        StderrLog { verbosity }
+    }
+}
+
+impl Observer for StderrLog {
+    fn event(&mut self, level: Level, message: fmt::Arguments<'_>) {
+        if self.verbosity.shows(level) {
+            eprintln!("[{}] {}", format!("{:?}", level).to_lowercase(), message);
+        }
+    }
+}
+
+/// Keeps every event, for tests and callers inspecting diagnostics.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Recorder {
+    pub events: Vec<(Level, String)>,
+}
+
+impl Recorder {
+    pub fn messages(&self, level: Level) -> Vec<&str> {
+        self.events
+            .iter()
+            .filter(|(event_level, _)| *event_level == level)
+            .map(|(_, message)| message.as_str())
+            .collect()
+    }
+}
+
+impl Observer for Recorder {
+    fn event(&mut self, level: Level, message: fmt::Arguments<'_>) {
+        self.events.push((level, message.to_string()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verbosity() {
+        assert_eq!(Verbosity::from_flag("-q"), Some(Verbosity::Quiet));
+        assert_eq!(Verbosity::from_flag("--verbose"), Some(Verbosity::Verbose));
+        assert_eq!(Verbosity::from_flag("-x"), None);
+
+        assert!(!Verbosity::Quiet.shows(Level::Info));
+        assert!(Verbosity::Normal.shows(Level::Info));
+        assert!(!Verbosity::Normal.shows(Level::Debug));
+        assert!(Verbosity::Verbose.shows(Level::Debug));
+    }
+
+    #[test]
+    fn test_recorder() {
+        let mut recorder = Recorder::default();
+        let observer: &mut dyn Observer = &mut recorder;
+
+        observer.event(Level::Info, format_args!("{} lines", 3));
+        observer.event(Level::Debug, format_args!("line {}", 1));
+        Silent.event(Level::Info, format_args!("dropped"));
+
+        assert_eq!(recorder.messages(Level::Info), vec!["3 lines"]);
+        assert_eq!(recorder.messages(Level::Debug), vec!["line 1"]);
+    }
+}
@@ -0,0 +1,280 @@
+use std::{error::Error, fmt::Write, str::FromStr};
+
+use super::json::Json;
+use super::observer::{Observer, Silent};
+use super::solution::Solution;
+
+/// How binaries print their results.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        match format {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!(
+                "unknown format '{}', expected json, csv or text",
+                format
+            )),
+        }
+    }
+}
+
+/// Intermediate data behind an answer, one row per record such as a game or a part.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Table {
+    pub name: String,
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<Json>>,
+}
+
+impl Table {
+    pub fn new(name: &str, columns: &[&str]) -> Table {
+        Table {
+            name: name.to_string(),
+            columns: columns.iter().map(|column| column.to_string()).collect(),
+            rows: Vec::new(),
+        }
+    }
+
+    /// Adds a row, with one value per column.
+    pub fn push(&mut self, row: Vec<Json>) {
+        debug_assert_eq!(row.len(), self.columns.len());
+        self.rows.push(row);
+    }
+}
+
+/// The answer to a part of a day, with the table it was computed from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    pub day: u32,
+    pub part: u32,
+    pub answer: String,
+    pub details: Table,
+}
+
+fn cell(value: &Json) -> String {
+    match value {
+        Json::Null => String::new(),
+        Json::String(string) => string.clone(),
+        value => value.to_string(),
+    }
+}
+
+fn csv_cell(value: &Json) -> String {
+    let cell = cell(value);
+
+    if cell.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell
+    }
+}
+
+impl Report {
+    /// `{"day", "part", "answer", <table name>: [{<column>: <value>}, ...]}`.
+    pub fn to_json(&self) -> Json {
+        let rows = self
+            .details
+            .rows
+            .iter()
+            .map(|row| {
+                Json::object(
+                    self.details
+                        .columns
+                        .iter()
+                        .cloned()
+                        .zip(row.iter().cloned()),
+                )
+            })
+            .collect();
+
+        let mut entries = vec![
+            ("day".to_string(), Json::from(self.day)),
+            ("part".to_string(), Json::from(self.part)),
+            ("answer".to_string(), Json::from(self.answer.as_str())),
+        ];
+        if !self.details.name.is_empty() {
+            entries.push((self.details.name.clone(), Json::Array(rows)));
+        }
+
+        Json::Object(entries)
+    }
+
+    /// One line per row of the table, each prefixed with the day, the part
+    /// and the answer so that the file stands alone as a single table.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("day,part,answer");
+        for column in &self.details.columns {
+            csv.push(',');
+            csv.push_str(&csv_cell(&Json::from(column.as_str())));
+        }
+        csv.push('\n');
+
+        let prefix = format!(
+            "{},{},{}",
+            self.day,
+            self.part,
+            csv_cell(&Json::from(self.answer.as_str()))
+        );
+        if self.details.rows.is_empty() {
+            let _ = writeln!(csv, "{}{}", prefix, ",".repeat(self.details.columns.len()));
+        }
+
+        for row in &self.details.rows {
+            csv.push_str(&prefix);
+            for value in row {
+                csv.push(',');
+                csv.push_str(&csv_cell(value));
+            }
+            csv.push('\n');
+        }
+
+        csv
+    }
+
+    /// The answer followed by the table, with aligned columns.
+    pub fn to_text(&self) -> String {
+        let mut text = format!("day {} part {}: {}\n", self.day, self.part, self.answer);
+        if self.details.columns.is_empty() {
+            return text;
+        }
+
+        let cells: Vec<Vec<String>> = self
+            .details
+            .rows
+            .iter()
+            .map(|row| row.iter().map(cell).collect())
+            .collect();
+        let widths: Vec<usize> = self
+            .details
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(column, name)| {
+                cells
+                    .iter()
+                    .map(|row| row[column].len())
+                    .fold(name.len(), usize::max)
+            })
+            .collect();
+
+        let _ = writeln!(text, "\n{}:", self.details.name);
+        for row in std::iter::once(&self.details.columns).chain(cells.iter()) {
+            let line: Vec<String> = row
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{:>width$}", cell, width = width))
+                .collect();
+            let _ = writeln!(text, "  {}", line.join("  ").trim_end());
+        }
+
+        text
+    }
+
+    pub fn render(&self, format: Format) -> String {
+        match format {
+            Format::Text => self.to_text(),
+            Format::Json => format!("{:#}\n", self.to_json()),
+            Format::Csv => self.to_csv(),
+        }
+    }
+}
+
+/// Parses `input` and reports on `part` of `S` with its details.
+pub fn report<S: Solution>(input: &str, day: u32, part: u32) -> Result<Report, Box<dyn Error>> {
+    report_observed::<S>(input, day, part, &mut Silent)
+}
+
+/// `report`, passing the diagnostics of the parse to `observer`.
+pub fn report_observed<S: Solution>(
+    input: &str,
+    day: u32,
+    part: u32,
+    observer: &mut dyn Observer,
+) -> Result<Report, Box<dyn Error>> {
+    if part != 1 && part != 2 {
+        return Err(format!("no part {}, parts are 1 and 2", part).into());
+    }
+
+    let solution = S::parse_observed(input, observer)?;
+    let answer = match part {
+        1 => solution.part_one().to_string(),
+        _ => solution.part_two().to_string(),
+    };
+
+    Ok(Report {
+        day,
+        part,
+        answer,
+        details: solution.details(part),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_report() -> Report {
+        let mut details = Table::new("games", &["id", "valid", "note"]);
+        details.push(vec![Json::from(1u32), Json::from(true), Json::from("a, b")]);
+        details.push(vec![Json::from(12u32), Json::from(false), Json::Null]);
+
+        Report {
+            day: 2,
+            part: 1,
+            answer: "1".to_string(),
+            details,
+        }
+    }
+
+    #[test]
+    fn test_format() {
+        assert_eq!("json".parse(), Ok(Format::Json));
+        assert_eq!("csv".parse(), Ok(Format::Csv));
+        assert_eq!("text".parse(), Ok(Format::Text));
+        assert!("yaml".parse::<Format>().is_err());
+    }
+
+    #[test]
+    fn test_render() {
+        let report = test_report();
+
+        assert_eq!(
+            report.to_json().to_string(),
+            r#"{"day":2,"part":1,"answer":"1","games":[{"id":1,"valid":true,"note":"a, b"},{"id":12,"valid":false,"note":null}]}"#
+        );
+        assert_eq!(
+            report.to_csv(),
+            "day,part,answer,id,valid,note\n2,1,1,1,true,\"a, b\"\n2,1,1,12,false,\n"
+        );
+        assert_eq!(
+            report.to_text(),
+            "day 2 part 1: 1\n\ngames:\n  id  valid  note\n   1   true  a, b\n  12  false\n"
+        );
+    }
+
+    #[test]
+    fn test_render_without_details() {
+        let report = Report {
+            details: Table::default(),
+            ..test_report()
+        };
+
+        assert_eq!(
+            report.to_json().to_string(),
+            r#"{"day":2,"part":1,"answer":"1"}"#
+        );
+        assert_eq!(report.to_csv(), "day,part,answer\n2,1,1\n");
+        assert_eq!(report.to_text(), "day 2 part 1: 1\n");
+    }
+}
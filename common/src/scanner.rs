@@ -0,0 +1,242 @@
+/// Line and column of a character, both starting at 1.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Default for Location {
+    fn default() -> Self {
+        Location { line: 1, column: 1 }
+    }
+}
+
+/// Saved scanner state to come back to with `Scanner::rewind`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Checkpoint {
+    cursor: usize,
+    location: Location,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Scanner {
+    cursor: usize,
+    location: Location,
+    characters: Vec<char>,
+}
+
+impl Scanner {
+    pub fn new(string: &str) -> Self {
+        Self {
+            cursor: 0,
+            location: Location::default(),
+            characters: string.chars().collect(),
+        }
+    }
+
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Location of the next character to be consumed.
+    pub fn location(&self) -> Location {
+        self.location
+    }
+
+    pub fn reset_cursor(&mut self) {
+        self.cursor = 0;
+        self.location = Location::default();
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.cursor == self.characters.len()
+    }
+
+    pub fn peek(&self) -> Option<char> {
+        self.peek_n(0)
+    }
+
+    /// The character `n` places after the next one, without consuming anything.
+    pub fn peek_n(&self, n: usize) -> Option<char> {
+        self.characters.get(self.cursor + n).copied()
+    }
+
+    /// Consumes the next character.
+    ///
+    /// Not an `Iterator`: its adaptors would shadow `take_while` and take the
+    /// scanner by value.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Option<char> {
+        let character = self.peek()?;
+        self.cursor += 1;
+
+        if character == '\n' {
+            self.location.line += 1;
+            self.location.column = 1;
+        } else {
+            self.location.column += 1;
+        }
+
+        Some(character)
+    }
+
+    /// Consumes the next character only if it matches `predicate`.
+    pub fn consume_if(&mut self, predicate: impl FnOnce(char) -> bool) -> Option<char> {
+        match self.peek() {
+            Some(character) if predicate(character) => self.next(),
+            _ => None,
+        }
+    }
+
+    /// Consumes characters as long as they match `predicate`.
+    pub fn take_while(&mut self, mut predicate: impl FnMut(char) -> bool) -> String {
+        let mut taken = String::new();
+
+        while let Some(character) = self.consume_if(&mut predicate) {
+            taken.push(character);
+        }
+
+        taken
+    }
+
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            cursor: self.cursor,
+            location: self.location,
+        }
+    }
+
+    pub fn rewind(&mut self, checkpoint: Checkpoint) {
+        self.cursor = checkpoint.cursor;
+        self.location = checkpoint.location;
+    }
+
+    pub fn transform<T>(&mut self, cb: impl FnOnce(&char) -> Option<T>) -> Option<T> {
+        let output = cb(&self.peek()?)?;
+        self.next();
+
+        Some(output)
+    }
+
+    /// Transforms characters until `cb` returns `None` or the input runs out.
+    ///
+    /// The character `cb` rejected is left unconsumed.
+    pub fn scan<T>(&mut self, mut cb: impl FnMut(&char) -> Option<T>) -> Vec<T> {
+        let mut outputs = Vec::new();
+
+        while let Some(output) = self.transform(&mut cb) {
+            outputs.push(output);
+        }
+
+        outputs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = "467..114..\n...*......\n..35..633.";
+
+    #[test]
+    fn test_transform() {
+        let lines: Vec<&str> = TEST_INPUT.lines().collect();
+
+        let mut scanner = Scanner::new(lines[0]);
+        assert_eq!(scanner.cursor, 0);
+
+        let first_char = scanner.transform(|character| Some(*character));
+
+        assert_eq!(first_char.unwrap(), '4');
+        assert_eq!(scanner.cursor, 1);
+
+        scanner.reset_cursor();
+
+        assert_eq!(scanner.cursor, 0);
+    }
+
+    #[test]
+    fn test_transform_line() {
+        let lines: Vec<&str> = TEST_INPUT.lines().collect();
+
+        let mut scanner = Scanner::new(lines[0]);
+        let mut line_string = String::new();
+
+        while !scanner.is_done() {
+            let curr_char = scanner.transform(|character| Some(*character));
+            //
+            match curr_char {
+                Some(element) => line_string.push(element),
+                None => continue,
+            };
+        }
+
+        assert_eq!(line_string, "467..114..".to_string());
+    }
+
+    #[test]
+    fn test_scan_stops() {
+        let mut scanner = Scanner::new("467..114");
+
+        let digits = scanner.scan(|character| character.to_digit(10));
+        assert_eq!(digits, vec![4, 6, 7]);
+        assert_eq!(scanner.peek(), Some('.'));
+
+        assert_eq!(scanner.scan(|_| None::<char>), vec![]);
+        assert_eq!(scanner.cursor(), 3);
+
+        let rest = scanner.scan(|character| Some(*character));
+        assert_eq!(rest.len(), 5);
+        assert!(scanner.is_done());
+        assert!(scanner.scan(|character| Some(*character)).is_empty());
+    }
+
+    #[test]
+    fn test_peek_and_consume() {
+        let mut scanner = Scanner::new("12*");
+
+        assert_eq!(scanner.peek(), Some('1'));
+        assert_eq!(scanner.peek_n(2), Some('*'));
+        assert_eq!(scanner.peek_n(3), None);
+
+        assert_eq!(scanner.consume_if(|character| character == '*'), None);
+        assert_eq!(
+            scanner.take_while(|character| character.is_ascii_digit()),
+            "12"
+        );
+        assert_eq!(scanner.consume_if(|character| character == '*'), Some('*'));
+        assert_eq!(scanner.next(), None);
+        assert_eq!(scanner.take_while(|_| true), "");
+    }
+
+    #[test]
+    fn test_location() {
+        let mut scanner = Scanner::new("ab\ncd");
+
+        assert_eq!(scanner.location(), Location { line: 1, column: 1 });
+        scanner.take_while(|character| character != '\n');
+        assert_eq!(scanner.location(), Location { line: 1, column: 3 });
+
+        scanner.next();
+        scanner.next();
+        assert_eq!(scanner.location(), Location { line: 2, column: 2 });
+
+        scanner.reset_cursor();
+        assert_eq!(scanner.location(), Location::default());
+    }
+
+    #[test]
+    fn test_rewind() {
+        let mut scanner = Scanner::new("..35\n617*");
+        scanner.take_while(|character| character == '.');
+
+        let checkpoint = scanner.checkpoint();
+        assert_eq!(scanner.take_while(|_| true), "35\n617*");
+        assert_eq!(scanner.location(), Location { line: 2, column: 5 });
+
+        scanner.rewind(checkpoint);
+        assert_eq!(scanner.cursor(), 2);
+        assert_eq!(scanner.location(), Location { line: 1, column: 3 });
+        assert_eq!(scanner.peek(), Some('3'));
+    }
+}
@@ -0,0 +1,126 @@
+use std::{
+    env,
+    error::Error,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use super::diff::format_diff;
+
+/// Environment variable that, when set to anything but `0`, makes snapshot
+/// checks write the current value instead of comparing with it.
+pub const BLESS_VAR: &str = "AOC_BLESS";
+
+/// A plain text form of a parsed structure, stable across runs so that it can
+/// be committed and compared by `assert_snapshot`.
+pub trait Snapshot {
+    fn snapshot(&self) -> String;
+}
+
+#[derive(Debug)]
+pub enum SnapshotError {
+    Missing(PathBuf),
+    Mismatch { path: PathBuf, diff: String },
+    Io(PathBuf, io::Error),
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SnapshotError::Missing(path) => write!(
+                f,
+                "no snapshot at '{}', run again with {}=1 to create it",
+                path.display(),
+                BLESS_VAR
+            ),
+            SnapshotError::Mismatch { path, diff } => write!(
+                f,
+                "snapshot '{}' does not match, run again with {}=1 to accept the changes:\n{}",
+                path.display(),
+                BLESS_VAR,
+                diff
+            ),
+            SnapshotError::Io(path, err) => {
+                write!(f, "cannot access snapshot '{}': {}", path.display(), err)
+            }
+        }
+    }
+}
+
+impl Error for SnapshotError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SnapshotError::Io(_, err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// Compares `actual` with the snapshot stored at `path`, or stores it there
+/// when `bless` is set.
+pub fn check_snapshot(path: &Path, actual: &str, bless: bool) -> Result<(), SnapshotError> {
+    let io_error = |err| SnapshotError::Io(path.to_path_buf(), err);
+
+    let expected = match fs::read_to_string(path) {
+        Ok(expected) => Some(expected),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => None,
+        Err(err) => return Err(io_error(err)),
+    };
+    if expected.as_deref() == Some(actual) {
+        return Ok(());
+    }
+
+    if bless {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(io_error)?;
+        }
+        return fs::write(path, actual).map_err(io_error);
+    }
+
+    match expected {
+        Some(expected) => Err(SnapshotError::Mismatch {
+            path: path.to_path_buf(),
+            diff: format_diff(&expected, actual, 2),
+        }),
+        None => Err(SnapshotError::Missing(path.to_path_buf())),
+    }
+}
+
+/// Panics with a readable diff when the snapshot of `value` differs from the
+/// one stored at `path`, blessing it instead when `AOC_BLESS` is set.
+#[track_caller]
+pub fn assert_snapshot(path: impl AsRef<Path>, value: &impl Snapshot) {
+    let bless = env::var(BLESS_VAR).is_ok_and(|bless| !bless.is_empty() && bless != "0");
+
+    if let Err(err) = check_snapshot(path.as_ref(), &value.snapshot(), bless) {
+        panic!("{}", err);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::process;
+
+    use super::*;
+
+    #[test]
+    fn test_check_snapshot() {
+        let dir = env::temp_dir().join(format!("aoc-snapshot-{}", process::id()));
+        let path = dir.join("numbers.snap");
+
+        let err = check_snapshot(&path, "1\n2\n", false).unwrap_err();
+        assert!(matches!(err, SnapshotError::Missing(_)));
+
+        check_snapshot(&path, "1\n2\n", true).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1\n2\n");
+        check_snapshot(&path, "1\n2\n", false).unwrap();
+
+        match check_snapshot(&path, "1\n3\n", false).unwrap_err() {
+            SnapshotError::Mismatch { diff, .. } => assert_eq!(diff, "  1\n- 2\n+ 3\n"),
+            err => panic!("unexpected error: {}", err),
+        }
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1\n2\n");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
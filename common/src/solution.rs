@@ -0,0 +1,89 @@
+use std::{error::Error, fmt::Display};
+
+use super::observer::Observer;
+use super::report::Table;
+
+/// A day of the calendar: its parsed input and the answers to both parts.
+///
+/// Parsing is the only step allowed to fail, the parts work on a value that
+/// is already known to be valid.
+pub trait Solution: Sized {
+    type PartOne: Display;
+    type PartTwo: Display;
+    type Error: Error + 'static;
+
+    fn parse(input: &str) -> Result<Self, Self::Error>;
+
+    /// `parse`, reporting what the input holds to `observer`. Reports
+    /// nothing by default.
+    fn parse_observed(input: &str, _observer: &mut dyn Observer) -> Result<Self, Self::Error> {
+        Self::parse(input)
+    }
+
+    fn part_one(&self) -> Self::PartOne;
+
+    fn part_two(&self) -> Self::PartTwo;
+
+    /// The intermediate data behind the answer to `part`, none by default.
+    fn details(&self, _part: u32) -> Table {
+        Table::default()
+    }
+}
+
+/// Parses `input` and solves `part` of `S`, for callers picking a day at runtime.
+pub fn solve<S: Solution>(input: &str, part: u32) -> Result<String, Box<dyn Error>> {
+    if part != 1 && part != 2 {
+        return Err(format!("no part {}, parts are 1 and 2", part).into());
+    }
+
+    let solution = S::parse(input)?;
+
+    match part {
+        1 => Ok(solution.part_one().to_string()),
+        _ => Ok(solution.part_two().to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{ParseError, Scanner};
+    use super::*;
+
+    struct Numbers(Vec<u32>);
+
+    impl Solution for Numbers {
+        type PartOne = u32;
+        type PartTwo = String;
+        type Error = ParseError;
+
+        fn parse(input: &str) -> Result<Self, Self::Error> {
+            let mut scanner = Scanner::new(input);
+            let numbers = scanner.separated_by(",", |scanner| scanner.number())?;
+
+            scanner.end()?;
+            Ok(Numbers(numbers))
+        }
+
+        fn part_one(&self) -> u32 {
+            self.0.iter().sum()
+        }
+
+        fn part_two(&self) -> String {
+            format!("{:?}", self.0.iter().max())
+        }
+    }
+
+    #[test]
+    fn test_solve() {
+        assert_eq!(solve::<Numbers>("1, 2, 3", 1).unwrap(), "6");
+        assert_eq!(solve::<Numbers>("1, 2, 3", 2).unwrap(), "Some(3)");
+
+        let err = solve::<Numbers>("1, x", 1).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 4: expected a number, found 'x'"
+        );
+
+        assert!(solve::<Numbers>("1", 3).is_err());
+    }
+}
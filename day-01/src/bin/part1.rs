@@ -1,81 +1,82 @@
-use std::fs;
+use std::{env, process};
 
-#[derive(Debug)]
-struct Coordinate {
-    pub first: u32,
-    pub last: u32,
-}
+use common::{CommonArgs, Format, Input, StderrLog, Verbosity};
+use day01::{Calibration, CalibrationSet, NUMERALS};
 
-impl Coordinate {
-    fn to_int(&self) -> u32 {
-        return self.first * 10 + self.last;
-    }
-}
+const DEFAULT_INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/bin/input1.txt");
 
-fn get_coodinate(line: &str) -> Option<Coordinate> {
-    if line.len() == 0 {
-        return None;
-    }
-    if line.len() < 2 {
-        if line.chars().next().unwrap().is_numeric() {
-            return Some(Coordinate {
-                first: line.parse().unwrap(),
-                last: line.parse().unwrap(),
-            });
-        }
+fn print_usage() {
+    println!("usage: day01-part1 [-v|-q] [--format json|csv|text] [input]\n");
+    println!(
+        "  input                      puzzle input file, '-' for stdin (defaults to the committed input)"
+    );
+    println!("  --format json|csv|text     print the answer and the value of every line");
+    println!("  -v, --verbose              also print the coordinate of every line to stderr");
+    println!("  -q, --quiet                only print the answer");
+}
 
-        return None;
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if CommonArgs::wants_help(&args) {
+        print_usage();
+        return;
     }
 
-    let mut current_slice = line;
+    let options = match CommonArgs::parse_shared(&args) {
+        Ok(options) => options,
+        Err(err) => {
+            eprintln!("error: {}\n", err);
+            print_usage();
+            process::exit(1);
+        }
+    };
 
-    if current_slice.chars().next().unwrap().is_numeric()
-        && current_slice.chars().last().unwrap().is_numeric()
-    {
-        return Some(Coordinate {
-            first: current_slice.chars().next().unwrap().to_digit(10).unwrap(),
-            last: current_slice.chars().last().unwrap().to_digit(10).unwrap(),
-        });
-    }
+    let input = Input::locate(options.input.as_deref(), 1, DEFAULT_INPUT);
+    let content = match input.read() {
+        Ok(content) => content,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            process::exit(1);
+        }
+    };
 
-    if !current_slice.chars().next().unwrap().is_numeric() {
-        current_slice = &current_slice[1..];
+    if options.format != Format::Text {
+        match common::report::<Calibration>(&content, 1, 1) {
+            Ok(report) => print!("{}", report.render(options.format)),
+            Err(err) => {
+                eprintln!("error: input {}: {}", input, err);
+                process::exit(1);
+            }
+        }
+        return;
     }
 
-    if !current_slice.chars().last().unwrap().is_numeric() {
-        current_slice = &current_slice[0..current_slice.len() - 1];
+    if options.verbosity != Verbosity::Quiet {
+        println!("Hello, Advent of Code 2023!\n");
     }
 
-    //println!("current_slice is: {:?}", current_slice);
+    let mut log = StderrLog::new(options.verbosity);
+    let calibration = CalibrationSet::parse_observed(&content, &NUMERALS, &mut log);
 
-    return get_coodinate(current_slice);
+    match calibration.compute() {
+        Ok(solution) => println!("sum of all coordinates: {}", solution),
+        Err(err) => {
+            eprintln!("error: input {}: {}", input, err);
+            process::exit(1);
+        }
+    }
 }
 
-fn main() -> Result<(), std::io::Error> {
-    println!("Hello, Advent of Code 2023!\n");
-    let content = fs::read_to_string("src/bin/input1.txt")?;
-    let lines: Vec<&str> = content.lines().collect();
-    println!("total lines found: {}", lines.len());
-    println!("first line found: {}", lines[1]);
-    let test_coord = get_coodinate(lines[1]).unwrap().to_int();
-    println!("test_coord is: {:?}", test_coord);
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use day01::CalibrationSet;
 
-    println!("+++++++++++++");
-    println!("parsing coordinates...");
+    #[test]
+    fn test_solution() {
+        let content = "1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet";
+        let calibration = CalibrationSet::parse_with(content, &NUMERALS);
 
-    let mut total = 0;
-
-    for line in 0..lines.len() {
-        let current_coordinate = get_coodinate(lines[line]);
-        match current_coordinate {
-            Some(coord) => {
-                println!("current coord: {:?}", coord);
-                total += coord.to_int();
-            }
-            None => println!("No coordinate on this line"),
-        }
+        assert_eq!(calibration.compute().unwrap(), 142);
     }
-
-    println!("sum of all coordinates: {:?}", total);
-    Ok(())
 }
@@ -0,0 +1,255 @@
+use common::{Json, Level, Observer, ParseError, Scanner, Silent, Snapshot, Solution, Table};
+
+use super::coordinate::Coordinate;
+use super::digit::{Spellings, DIGITS, NUMERALS};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CalibrationSet {
+    Empty,
+    Coordinates(Vec<Coordinate>),
+}
+
+impl Default for CalibrationSet {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CalibrationSet {
+    pub fn new() -> CalibrationSet {
+        CalibrationSet::Empty
+    }
+
+    /// One coordinate per line of `content`, reading digits among `spellings`.
+    pub fn parse_with(content: &str, spellings: &Spellings) -> CalibrationSet {
+        CalibrationSet::parse_observed(content, spellings, &mut Silent)
+    }
+
+    /// `parse_with`, reporting the coordinate of every line to `observer`.
+    pub fn parse_observed(
+        content: &str,
+        spellings: &Spellings,
+        observer: &mut dyn Observer,
+    ) -> CalibrationSet {
+        let mut calibration = CalibrationSet::new();
+        let mut total_lines = 0;
+
+        for (line_number, line) in content.lines().enumerate() {
+            let coordinate = Coordinate::parse_with(line, spellings);
+            observer.event(
+                Level::Debug,
+                format_args!(
+                    "line {} {:?}: {:?} = {}",
+                    line_number + 1,
+                    line,
+                    coordinate,
+                    coordinate.to_int()
+                ),
+            );
+
+            calibration = calibration.add(coordinate);
+            total_lines += 1;
+        }
+
+        observer.event(
+            Level::Info,
+            format_args!("total lines found: {}", total_lines),
+        );
+        calibration
+    }
+
+    pub fn add(&self, coordinate: Coordinate) -> CalibrationSet {
+        match *self {
+            CalibrationSet::Empty => CalibrationSet::Coordinates(vec![coordinate]),
+            CalibrationSet::Coordinates(ref coordinates_list) => {
+                let mut new_list = coordinates_list.to_vec();
+                new_list.push(coordinate);
+                CalibrationSet::Coordinates(new_list)
+            }
+        }
+    }
+
+    pub fn compute(&self) -> Result<i32, &str> {
+        match *self {
+            CalibrationSet::Empty => Err("compute error: calibration set is empty"),
+            CalibrationSet::Coordinates(ref coordinates_list) => {
+                let new_list = coordinates_list.to_vec();
+                let mut result: i32 = 0;
+                for coord in new_list {
+                    result += coord.to_int()
+                }
+                Ok(result)
+            }
+        }
+    }
+}
+
+/// One `line N: <coordinate> = <value>` line per coordinate.
+impl Snapshot for CalibrationSet {
+    fn snapshot(&self) -> String {
+        match self {
+            CalibrationSet::Empty => "empty\n".to_string(),
+            CalibrationSet::Coordinates(coordinates) => coordinates
+                .iter()
+                .enumerate()
+                .map(|(number, coordinate)| {
+                    format!(
+                        "line {}: {:?} = {}\n",
+                        number + 1,
+                        coordinate,
+                        coordinate.to_int()
+                    )
+                })
+                .collect(),
+        }
+    }
+}
+
+/// The calibration document of the puzzle, one line per coordinate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Calibration {
+    pub lines: Vec<String>,
+}
+
+impl Calibration {
+    pub fn sum_with(&self, spellings: &Spellings) -> i32 {
+        let mut calibration = CalibrationSet::new();
+
+        for line in &self.lines {
+            calibration = calibration.add(Coordinate::parse_with(line, spellings));
+        }
+
+        calibration.compute().unwrap_or(0)
+    }
+
+    /// The calibration value of every line, numbered from 1.
+    pub fn values_with(&self, spellings: &Spellings) -> Table {
+        let mut table = Table::new("lines", &["line", "text", "value"]);
+
+        for (number, line) in self.lines.iter().enumerate() {
+            table.push(vec![
+                Json::from(number + 1),
+                Json::from(line.as_str()),
+                Json::from(Coordinate::parse_with(line, spellings).to_int() as i64),
+            ]);
+        }
+
+        table
+    }
+}
+
+impl Solution for Calibration {
+    type PartOne = i32;
+    type PartTwo = i32;
+    type Error = ParseError;
+
+    fn parse(input: &str) -> Result<Self, Self::Error> {
+        if input.trim().is_empty() {
+            return Err(Scanner::new(input).error("a calibration line"));
+        }
+
+        Ok(Calibration {
+            lines: input.lines().map(|line| line.to_string()).collect(),
+        })
+    }
+
+    fn parse_observed(input: &str, observer: &mut dyn Observer) -> Result<Self, Self::Error> {
+        let calibration = Calibration::parse(input)?;
+
+        for (line_number, line) in calibration.lines.iter().enumerate() {
+            observer.event(
+                Level::Debug,
+                format_args!("line {} {:?}", line_number + 1, line),
+            );
+        }
+        observer.event(
+            Level::Info,
+            format_args!("total lines found: {}", calibration.lines.len()),
+        );
+
+        Ok(calibration)
+    }
+
+    fn part_one(&self) -> i32 {
+        self.sum_with(&NUMERALS)
+    }
+
+    fn part_two(&self) -> i32 {
+        self.sum_with(&DIGITS)
+    }
+
+    fn details(&self, part: u32) -> Table {
+        match part {
+            1 => self.values_with(&NUMERALS),
+            _ => self.values_with(&DIGITS),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_calibration() {
+        let content = "1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet";
+        assert_eq!(
+            CalibrationSet::parse_with(content, &NUMERALS).compute(),
+            Ok(142)
+        );
+
+        let content = "two1nine\neightwothree\nabcone2threexyz";
+        assert_eq!(
+            CalibrationSet::parse_with(content, &DIGITS).compute(),
+            Ok(29 + 83 + 13)
+        );
+
+        assert!(CalibrationSet::parse_with("", &DIGITS).compute().is_err());
+
+        let mut recorder = common::Recorder::default();
+        CalibrationSet::parse_observed("two1nine\nabc", &DIGITS, &mut recorder);
+        assert_eq!(
+            recorder.messages(Level::Debug),
+            vec![
+                "line 1 \"two1nine\": Full(2, 9) = 29",
+                "line 2 \"abc\": Full(0, 0) = 0"
+            ]
+        );
+        assert_eq!(recorder.messages(Level::Info), vec!["total lines found: 2"]);
+    }
+
+    #[test]
+    fn test_calibration_snapshots() {
+        let content = std::fs::read_to_string("src/bin/test_input.txt").unwrap();
+
+        common::assert_snapshot(
+            "src/snapshots/test_input_numerals.snap",
+            &CalibrationSet::parse_with(&content, &NUMERALS),
+        );
+        common::assert_snapshot(
+            "src/snapshots/test_input_digits.snap",
+            &CalibrationSet::parse_with(&content, &DIGITS),
+        );
+    }
+
+    #[test]
+    fn test_solution() {
+        let calibration = Calibration::parse("two1nine\n7pqrstsixteen").unwrap();
+
+        assert_eq!(calibration.part_one(), 11 + 77);
+        assert_eq!(calibration.part_two(), 29 + 76);
+        assert!(Calibration::parse("\n").is_err());
+
+        let details = calibration.details(2);
+        assert_eq!(details.columns, vec!["line", "text", "value"]);
+        assert_eq!(
+            details.rows[0],
+            vec![
+                Json::from(1usize),
+                Json::from("two1nine"),
+                Json::from(29i64)
+            ]
+        );
+        assert_eq!(details.rows[1][2], Json::from(76i64));
+    }
+}
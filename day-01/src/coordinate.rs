@@ -0,0 +1,69 @@
+use super::digit::{Spellings, DIGITS};
+use super::line::Line;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Coordinate {
+    Full(i32, i32),
+    Half(i32),
+    Empty,
+}
+
+impl Default for Coordinate {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Coordinate {
+    pub fn new() -> Coordinate {
+        Coordinate::Empty
+    }
+
+    pub fn add(&self, digit: i32) -> Coordinate {
+        match *self {
+            Coordinate::Empty => Coordinate::Half(digit),
+            Coordinate::Half(first_coord) => Coordinate::Full(first_coord, digit),
+            Coordinate::Full(first_coord, last_coord) => Coordinate::Full(first_coord, last_coord),
+        }
+    }
+
+    pub fn parse_str(line: &str) -> Coordinate {
+        Coordinate::parse_with(line, &DIGITS)
+    }
+
+    pub fn parse_with(line: &str, spellings: &Spellings) -> Coordinate {
+        //
+        let mut coordinates = Coordinate::new();
+        let current_line = Line::parse_with(line, spellings);
+
+        if current_line.is_empty() {
+            return Coordinate::Empty;
+        }
+
+        coordinates = coordinates.add(current_line.head.value());
+        coordinates = coordinates.add(current_line.tail.value());
+
+        coordinates
+    }
+
+    pub fn to_int(self) -> i32 {
+        match self {
+            Coordinate::Empty => 0,
+            Coordinate::Half(first_coord) => first_coord * 10 + first_coord,
+            Coordinate::Full(first_coord, last_coord) => first_coord * 10 + last_coord,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_coordinate() {
+        assert_eq!(Coordinate::parse_str("eight"), Coordinate::Full(8, 8));
+        assert_eq!(Coordinate::parse_str("eight").to_int(), 88);
+        assert_eq!(Coordinate::parse_str("").to_int(), 0);
+        assert_eq!(Coordinate::new().add(4).to_int(), 44);
+    }
+}
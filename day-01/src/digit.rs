@@ -0,0 +1,93 @@
+use common::Scanner;
+
+pub type Spellings = [(&'static str, i32)];
+
+/// Digits written as numerals only, the first part of the puzzle.
+pub const NUMERALS: [(&str, i32); 9] = [
+    ("1", 1),
+    ("2", 2),
+    ("3", 3),
+    ("4", 4),
+    ("5", 5),
+    ("6", 6),
+    ("7", 7),
+    ("8", 8),
+    ("9", 9),
+];
+
+/// Digits written as numerals or spelled out with letters.
+pub const DIGITS: [(&str, i32); 18] = [
+    ("1", 1),
+    ("2", 2),
+    ("3", 3),
+    ("4", 4),
+    ("5", 5),
+    ("6", 6),
+    ("7", 7),
+    ("8", 8),
+    ("9", 9),
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Digit {
+    Empty,
+    Number(String),
+    NaN,
+}
+
+impl Digit {
+    /// The digit spelled at the scanner's cursor, if any, without consuming it
+    /// so that overlapping spellings like `oneight` yield both digits.
+    pub fn parse(scanner: &mut Scanner, spellings: &Spellings) -> Option<Digit> {
+        let checkpoint = scanner.checkpoint();
+
+        for (spelling, _) in spellings {
+            if scanner.literal(spelling).is_ok() {
+                scanner.rewind(checkpoint);
+                return Some(Digit::Number(spelling.to_string()));
+            }
+        }
+
+        None
+    }
+
+    pub fn value(&self) -> i32 {
+        match self {
+            Digit::Empty => 0,
+            Digit::NaN => 0,
+            Digit::Number(val) => DIGITS
+                .iter()
+                .find(|(spelling, _)| spelling == val)
+                .map_or(0, |(_, value)| *value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_digit() {
+        let mut scanner = Scanner::new("one1");
+
+        assert_eq!(
+            Digit::parse(&mut scanner, &DIGITS),
+            Some(Digit::Number("one".to_string()))
+        );
+        assert_eq!(scanner.cursor(), 0);
+        assert_eq!(Digit::parse(&mut scanner, &NUMERALS), None);
+
+        assert_eq!(Digit::Number("seven".to_string()).value(), 7);
+        assert_eq!(Digit::NaN.value(), 0);
+    }
+}
@@ -0,0 +1,9 @@
+mod calibration;
+mod coordinate;
+mod digit;
+mod line;
+
+pub use calibration::*;
+pub use coordinate::*;
+pub use digit::*;
+pub use line::*;
@@ -0,0 +1,87 @@
+use common::Scanner;
+
+use super::digit::{Digit, Spellings, DIGITS};
+
+#[derive(Debug)]
+pub struct Line {
+    pub content: String,
+    pub head: Digit,
+    pub tail: Digit,
+}
+
+impl Default for Line {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Line {
+    pub fn new() -> Line {
+        Line {
+            content: "".to_string(),
+            head: Digit::Empty,
+            tail: Digit::Empty,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        matches!(
+            (self.content.is_empty(), &self.head, &self.tail),
+            (true, Digit::Empty, Digit::Empty)
+        )
+    }
+
+    /// First and last digits of the line, spelled out or not.
+    pub fn parse_str(line: &str) -> Line {
+        Line::parse_with(line, &DIGITS)
+    }
+
+    /// First and last digits of the line, among `spellings`.
+    pub fn parse_with(line: &str, spellings: &Spellings) -> Line {
+        if line.is_empty() {
+            return Line::new();
+        }
+
+        let mut scanner = Scanner::new(line);
+        let mut head: Option<Digit> = None;
+        let mut tail: Option<Digit> = None;
+
+        while !scanner.is_done() {
+            if let Some(digit) = Digit::parse(&mut scanner, spellings) {
+                head.get_or_insert_with(|| digit.clone());
+                tail = Some(digit);
+            }
+
+            scanner.next();
+        }
+
+        Line {
+            content: line.to_string(),
+            head: head.unwrap_or(Digit::NaN),
+            tail: tail.unwrap_or(Digit::NaN),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::NUMERALS;
+    use super::*;
+
+    #[test]
+    fn test_line_digits() {
+        let line = Line::parse_str("7pqrstsixteen");
+        assert_eq!((line.head.value(), line.tail.value()), (7, 6));
+
+        let line = Line::parse_str("xoneightx");
+        assert_eq!((line.head.value(), line.tail.value()), (1, 8));
+
+        let line = Line::parse_with("7pqrstsixteen", &NUMERALS);
+        assert_eq!((line.head.value(), line.tail.value()), (7, 7));
+
+        let line = Line::parse_str("nodigit");
+        assert_eq!((line.head, line.tail), (Digit::NaN, Digit::NaN));
+
+        assert!(Line::parse_str("").is_empty());
+    }
+}
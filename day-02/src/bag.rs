@@ -0,0 +1,167 @@
+use common::{Json, Scanner, Table};
+
+use super::*;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BagReport {
+    pub bag: Subset,
+    pub id_sum: i32,
+    pub valid_games: Vec<i32>,
+}
+
+impl BagReport {
+    pub fn check(bag: &Subset, games: &[Game]) -> BagReport {
+        let valid_games: Vec<i32> = games
+            .iter()
+            .filter(|game| game.is_valid_for_subset(bag))
+            .map(|game| game.id)
+            .collect();
+
+        BagReport {
+            bag: bag.clone(),
+            id_sum: valid_games.iter().sum(),
+            valid_games,
+        }
+    }
+}
+
+/// The bag of the puzzle: 12 red, 13 green and 14 blue cubes.
+pub fn default_bag() -> Subset {
+    let mut cubes_bag = Subset::new();
+    cubes_bag.add(Cube::Red(12));
+    cubes_bag.add(Cube::Green(13));
+    cubes_bag.add(Cube::Blue(14));
+
+    cubes_bag
+}
+
+pub fn parse_bag(line: &str) -> Result<Subset, String> {
+    let mut scanner = Scanner::new(line.trim());
+
+    Subset::parse(&mut scanner)
+        .and_then(|bag| scanner.end().map(|_| bag))
+        .map_err(|err| format!("invalid bag '{}': {}", line, err))
+}
+
+pub fn parse_bag_list(content: &str) -> Result<Vec<Subset>, String> {
+    let mut bags: Vec<Subset> = Vec::new();
+
+    for line in content.lines() {
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        bags.push(parse_bag(line)?);
+    }
+
+    Ok(bags)
+}
+
+pub fn compare_bags(bags: &[Subset], games: &[Game]) -> Vec<BagReport> {
+    bags.iter()
+        .map(|bag| BagReport::check(bag, games))
+        .collect()
+}
+
+/// Whether each game is possible with each bag, one row per bag and game,
+/// every row carrying the id sum of its bag.
+pub fn bag_table(reports: &[BagReport], games: &[Game]) -> Table {
+    let mut table = Table::new("games", &["bag", "id_sum", "id", "valid"]);
+
+    for report in reports {
+        let bag = format!(
+            "{} red, {} green, {} blue",
+            report.bag.red.count(),
+            report.bag.green.count(),
+            report.bag.blue.count()
+        );
+
+        for game in games {
+            table.push(vec![
+                Json::from(bag.as_str()),
+                Json::from(report.id_sum as i64),
+                Json::from(game.id as i64),
+                Json::from(report.valid_games.contains(&game.id)),
+            ]);
+        }
+    }
+
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_games() -> Vec<Game> {
+        vec![
+            Game::parse_str("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green").unwrap(),
+            Game::parse_str("Game 2: 20 red, 2 green; 3 green, 4 blue").unwrap(),
+            Game::parse_str("Game 3: 1 red, 15 green; 1 blue").unwrap(),
+        ]
+    }
+
+    #[test]
+    fn test_parse_bag() {
+        let mut control_bag = Subset::new();
+        control_bag.add(Cube::Red(12));
+        control_bag.add(Cube::Green(13));
+        control_bag.add(Cube::Blue(14));
+
+        assert_eq!(parse_bag("12 red, 13 green, 14 blue").unwrap(), control_bag);
+        assert_eq!(parse_bag("14 blue,12 red,13 green").unwrap(), control_bag);
+    }
+
+    #[test]
+    fn test_bad_bag() {
+        assert!(parse_bag("12 red, 13 yellow").is_err());
+        assert!(parse_bag("12red").is_err());
+        assert!(parse_bag("").is_err());
+    }
+
+    #[test]
+    fn test_parse_bag_list() {
+        let content = "# default bag\n12 red, 13 green, 14 blue\n\n20 red, 20 green, 20 blue\n";
+        let bags = parse_bag_list(content).unwrap();
+
+        assert_eq!(bags.len(), 2);
+        assert_eq!(bags[1].red, Cube::Red(20));
+        assert!(parse_bag_list("12 red\n13 purple\n").is_err());
+    }
+
+    #[test]
+    fn test_compare_bags() {
+        let games = test_games();
+        let bags = vec![
+            parse_bag("12 red, 13 green, 14 blue").unwrap(),
+            parse_bag("20 red, 20 green, 20 blue").unwrap(),
+            parse_bag("1 red, 1 green, 1 blue").unwrap(),
+        ];
+
+        let reports = compare_bags(&bags, &games);
+
+        assert_eq!(reports.len(), 3);
+
+        let table = bag_table(&reports[..2], &games);
+        assert_eq!(table.rows.len(), 6);
+        assert_eq!(
+            table.rows[1],
+            vec![
+                Json::from("12 red, 13 green, 14 blue"),
+                Json::from(1i64),
+                Json::from(2i64),
+                Json::from(false)
+            ]
+        );
+        assert_eq!(table.rows[4][1], Json::from(6i64));
+        assert_eq!(table.rows[4][3], Json::from(true));
+        assert_eq!(reports[0].valid_games, vec![1]);
+        assert_eq!(reports[0].id_sum, 1);
+        assert_eq!(reports[1].valid_games, vec![1, 2, 3]);
+        assert_eq!(reports[1].id_sum, 6);
+        assert_eq!(reports[2].valid_games, Vec::<i32>::new());
+        assert_eq!(reports[2].id_sum, 0);
+    }
+}
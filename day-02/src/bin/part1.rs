@@ -1,18 +1,17 @@
-use std::{fs, i32};
+use std::{env, fs, process};
 
-use day02::{Cube, Game, Subset};
+use common::{CommonArgs, Format, Input, Report, StderrLog, Verbosity};
+use day02::{
+    bag_table, compare_bags, default_bag, parse_bag, parse_bag_list, parse_games_observed,
+    BagReport, Game, Subset,
+};
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct ValidatedGameList {
-    pub id_sum: i32,
-    pub games: Vec<Game>,
-    pub valid_games: Vec<Game>,
-}
+const DEFAULT_INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/bin/input1.txt");
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct MinimumCombinationGameList {
-    pub combinations_pow_sum: i32,
-    pub combinations: Vec<Subset>,
+struct Options {
+    bags: Vec<Subset>,
+    common: CommonArgs,
 }
 
 fn print_title() {
@@ -21,6 +20,53 @@ fn print_title() {
     println!("---------------------------------------");
 }
 
+fn print_usage() {
+    println!("usage: day02-part1 [-v|-q] [--bag \"12 red, 13 green, 14 blue\"]... [--bags <file>] [--format json|csv|text] [input]\n");
+    println!(
+        "  input           puzzle input file, '-' for stdin (defaults to the committed input)"
+    );
+    println!("  --bag <bag>     check games against this bag, can be repeated");
+    println!("  --bags <file>   read bags from a file, one bag per line ('#' for comments)");
+    println!("  --format json|csv|text");
+    println!(
+        "                  print every game checked against each bag with the id sum of the bag,"
+    );
+    println!("                  the answer being the id sum of the first bag");
+    println!("  -v, --verbose   also print per-record diagnostics to stderr");
+    println!("  -q, --quiet     print no diagnostics and no title");
+    println!("\nwhen no bag is given the puzzle bag (12 red, 13 green, 14 blue) is used");
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut bags: Vec<Subset> = Vec::new();
+
+    let common = CommonArgs::parse(args, |flag, rest| {
+        match flag {
+            "--bag" => match rest.next() {
+                Some(bag) => bags.push(parse_bag(bag)?),
+                None => return Err("missing value for '--bag'".to_string()),
+            },
+            "--bags" => match rest.next() {
+                Some(file_path) => {
+                    let content = fs::read_to_string(file_path)
+                        .map_err(|err| format!("cannot read bags file '{}': {}", file_path, err))?;
+                    bags.extend(parse_bag_list(&content)?);
+                }
+                None => return Err("missing value for '--bags'".to_string()),
+            },
+            _ => return Ok(false),
+        }
+
+        Ok(true)
+    })?;
+
+    if bags.is_empty() {
+        bags.push(default_bag());
+    }
+
+    Ok(Options { bags, common })
+}
+
 fn print_bag_composition(bag: &Subset) {
     println!("+-----------------------------+");
     println!("| BAG COMPOSITION:");
@@ -31,99 +77,180 @@ fn print_bag_composition(bag: &Subset) {
     println!("+-----------------------------+\n");
 }
 
-fn print_result(list: ValidatedGameList) {
-    let valid_games: Vec<i32> = list.valid_games.iter().map(|game| game.id).collect();
-    let valid_games_percentage: f32 = list.valid_games.len() as f32 / list.games.len() as f32;
+fn print_result(report: &BagReport, games: &[Game]) {
+    let valid_games_percentage: f32 = report.valid_games.len() as f32 / games.len() as f32;
     let percent_label = (valid_games_percentage * 100.0) as u32;
-    let all_games: Vec<i32> = list.games.iter().map(|game| game.id).collect();
 
     println!("+--------------------------------------");
     println!("| RESULTS:");
     println!("+--------------------------------------");
-    println!("| total sum of ids: {:?}", list.id_sum);
+    println!("| total sum of ids: {:?}", report.id_sum);
     println!(
         "| number of valid games: {:?} ({:?} %)",
-        list.valid_games.len(),
+        report.valid_games.len(),
         percent_label
     );
-    println!("| valid games: {:?}", valid_games);
-
-    println!("| number of games: {:?}", list.games.len());
-
-    println!("| all games: {:?}", all_games);
+    println!("| valid games: {:?}", report.valid_games);
+    println!("| number of games: {:?}", games.len());
     println!("+--------------------------------------\n");
 }
 
-fn check_games(games_lines: &Vec<&str>, cubes_bag: &Subset) -> ValidatedGameList {
-    let mut games_list: Vec<Game> = vec![];
-    let mut id_accumulator = 0;
-    let mut valid_games: Vec<Game> = vec![];
-
-    for game_str in games_lines {
-        let parsed_game = Game::parse_str(game_str);
-
-        match parsed_game {
-            Some(game) => {
-                //println!("game: {:?}", game);
-                if game.is_valid_for_subset(cubes_bag) {
-                    id_accumulator += game.id;
-                    valid_games.push(game.clone());
-                    games_list.push(game.clone());
-                    continue;
-                }
-                games_list.push(game.clone());
-            }
-            None => continue,
-        }
+fn print_comparison(reports: &[BagReport]) {
+    println!("+--------------------------------------");
+    println!("| BAGS COMPARISON:");
+    println!("+--------------------------------------");
+    println!("|   red | green |  blue | valid | id sum");
+    for report in reports {
+        println!(
+            "| {:>5} | {:>5} | {:>5} | {:>5} | {}",
+            report.bag.red.count(),
+            report.bag.green.count(),
+            report.bag.blue.count(),
+            report.valid_games.len(),
+            report.id_sum
+        );
     }
+    println!("+--------------------------------------\n");
+}
 
-    ValidatedGameList {
-        id_sum: id_accumulator,
-        games: games_list,
-        valid_games: valid_games,
+/// The answer for the first bag, with every game checked against each bag.
+fn bags_report(reports: &[BagReport], games: &[Game]) -> Report {
+    Report {
+        day: 2,
+        part: 1,
+        answer: reports[0].id_sum.to_string(),
+        details: bag_table(reports, games),
     }
 }
 
-fn main() -> Result<(), std::io::Error> {
-    print_title();
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if CommonArgs::wants_help(&args) {
+        print_title();
+        print_usage();
+        return;
+    }
 
-    let content = fs::read_to_string("src/bin/input1.txt")?;
-    //let content = fs::read_to_string("src/bin/test_input.txt")?;
-    let lines: Vec<&str> = content.lines().collect();
+    let options = match parse_args(&args) {
+        Ok(options) => options,
+        Err(err) => {
+            eprintln!("error: {}\n", err);
+            print_usage();
+            process::exit(1);
+        }
+    };
 
-    let mut cubes_bag = Subset::new();
-    cubes_bag.add(Cube::Red(12));
-    cubes_bag.add(Cube::Green(13));
-    cubes_bag.add(Cube::Blue(14));
+    let input = Input::locate(options.common.input.as_deref(), 2, DEFAULT_INPUT);
+    let content = match input.read() {
+        Ok(content) => content,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            process::exit(1);
+        }
+    };
 
-    print_bag_composition(&cubes_bag);
-    println!("### processing...");
+    let games = match parse_games_observed(&content, &mut StderrLog::new(options.common.verbosity))
+    {
+        Ok(games) => games,
+        Err(err) => {
+            eprintln!("error: input {}: {}", input, err);
+            process::exit(1);
+        }
+    };
+    let reports = compare_bags(&options.bags, &games);
 
-    let games_list = check_games(&lines, &cubes_bag);
+    if options.common.format != Format::Text {
+        print!(
+            "{}",
+            bags_report(&reports, &games).render(options.common.format)
+        );
+        return;
+    }
+
+    if options.common.verbosity != Verbosity::Quiet {
+        print_title();
+    }
+    println!("### processing...");
 
-    print_result(games_list);
+    for report in &reports {
+        print_bag_composition(&report.bag);
+        print_result(report, &games);
+    }
 
-    Ok(())
+    if reports.len() > 1 {
+        print_comparison(&reports);
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use day02::{parse_games, Cube};
 
     #[test]
     fn test_solution() {
-        //let content = fs::read_to_string("src/bin/input1.txt").unwrap();
         let content = fs::read_to_string("src/bin/test_input.txt").unwrap();
-        let lines: Vec<&str> = content.lines().collect();
 
-        let mut cubes_bag = Subset::new();
-        cubes_bag.add(Cube::Red(12));
-        cubes_bag.add(Cube::Green(13));
-        cubes_bag.add(Cube::Blue(14));
+        let games = parse_games(&content).unwrap();
+        let reports = compare_bags(&[default_bag()], &games);
+
+        assert_eq!(reports[0].id_sum, 8);
+        assert_eq!(reports[0].valid_games, vec![1, 2, 5]);
+    }
+
+    #[test]
+    fn test_bags_report() {
+        let games = parse_games("Game 1: 1 red\nGame 2: 15 blue").unwrap();
+        let bags = vec![
+            parse_bag("1 red, 1 green, 1 blue").unwrap(),
+            parse_bag("20 red, 20 green, 20 blue").unwrap(),
+        ];
+        let report = bags_report(&compare_bags(&bags, &games), &games);
+
+        assert_eq!(
+            report.to_csv(),
+            "day,part,answer,bag,id_sum,id,valid\n\
+             2,1,1,\"1 red, 1 green, 1 blue\",1,1,true\n\
+             2,1,1,\"1 red, 1 green, 1 blue\",1,2,false\n\
+             2,1,1,\"20 red, 20 green, 20 blue\",3,1,true\n\
+             2,1,1,\"20 red, 20 green, 20 blue\",3,2,true\n"
+        );
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let args: Vec<String> = vec![
+            "--bag".to_string(),
+            "12 red, 13 green, 14 blue".to_string(),
+            "--bag".to_string(),
+            "1 red".to_string(),
+        ];
+        let options = parse_args(&args).unwrap();
+
+        assert_eq!(options.bags.len(), 2);
+        assert_eq!(options.bags[0], default_bag());
+        assert_eq!(options.bags[1].red, Cube::Red(1));
+        assert_eq!(options.common.input, None);
+        assert_eq!(options.common.format, Format::Text);
+
+        let args: Vec<String> = vec!["--format".to_string(), "json".to_string()];
+        assert_eq!(parse_args(&args).unwrap().common.format, Format::Json);
+        assert!(parse_args(&["--format".to_string(), "yaml".to_string()]).is_err());
+
+        assert_eq!(
+            parse_args(&[]).unwrap(),
+            Options {
+                bags: vec![default_bag()],
+                common: CommonArgs::default(),
+            }
+        );
+        assert!(parse_args(&["--bag".to_string()]).is_err());
+        assert!(parse_args(&["--colour".to_string()]).is_err());
 
-        let games_list = check_games(&lines, &cubes_bag);
+        let options = parse_args(&["-".to_string()]).unwrap();
+        assert_eq!(options.common.input, Some("-".to_string()));
 
-        //assert_eq!(games_list.id_sum, 2563);
-        assert_eq!(games_list.id_sum, 8);
+        let args: Vec<String> = vec!["input.txt".to_string(), "other.txt".to_string()];
+        assert!(parse_args(&args).is_err());
     }
 }
@@ -1,6 +1,9 @@
-use std::{fs, i32};
+use std::{env, process};
 
-use day02::{Cube, Game, Subset};
+use common::{CommonArgs, Format, Input, StderrLog, Verbosity};
+use day02::{parse_games_observed, Game, Games, Subset};
+
+const DEFAULT_INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/bin/input1.txt");
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ValidatedGameList {
@@ -15,22 +18,22 @@ pub struct MinimumCombinationGameList {
     pub combinations: Vec<Subset>,
 }
 
+fn print_usage() {
+    println!("usage: day02-part2 [-v|-q] [--format json|csv|text] [input]\n");
+    println!(
+        "  input                      puzzle input file, '-' for stdin (defaults to the committed input)"
+    );
+    println!("  --format json|csv|text     print the answer and the smallest bag of every game");
+    println!("  -v, --verbose              also print per-record diagnostics to stderr");
+    println!("  -q, --quiet                print no diagnostics and no title");
+}
+
 fn print_title() {
-    println!("Advent of Code 2023 - Day 02 [PART 1]\n");
+    println!("Advent of Code 2023 - Day 02 [PART 2]\n");
     println!("coded with 💛️💙️ by tanque");
     println!("---------------------------------------");
 }
 
-fn print_bag_composition(bag: &Subset) {
-    println!("+-----------------------------+");
-    println!("| BAG COMPOSITION:");
-    println!("+-----------------------------+");
-    println!("| RED cubes: {:?}", bag.red.count());
-    println!("| GREEN cubes: {:?}", bag.green.count());
-    println!("| BLUE cubes: {:?}", bag.blue.count());
-    println!("+-----------------------------+\n");
-}
-
 fn print_result(list: &MinimumCombinationGameList) {
     let power_sum = list.combinations_pow_sum;
     let all_combinations = &list.combinations;
@@ -43,23 +46,14 @@ fn print_result(list: &MinimumCombinationGameList) {
     println!("+--------------------------------------\n");
 }
 
-fn check_games(games_lines: &Vec<&str>) -> MinimumCombinationGameList {
-    let mut games_list: Vec<Game> = vec![];
+fn check_games(games: &[Game]) -> MinimumCombinationGameList {
     let mut combinations_pow_accumulator = 0;
     let mut combinations_list: Vec<Subset> = vec![];
 
-    for game_str in games_lines {
-        let parsed_game = Game::parse_str(game_str);
-
-        match parsed_game {
-            Some(game) => {
-                //println!("game: {:?}", game);
-                let game_minimum_combination = game.minimum_valid_combination();
-                combinations_pow_accumulator += game_minimum_combination.pow();
-                combinations_list.push(game_minimum_combination);
-            }
-            None => continue,
-        }
+    for game in games {
+        let game_minimum_combination = game.minimum_valid_combination();
+        combinations_pow_accumulator += game_minimum_combination.pow();
+        combinations_list.push(game_minimum_combination);
     }
 
     MinimumCombinationGameList {
@@ -68,31 +62,72 @@ fn check_games(games_lines: &Vec<&str>) -> MinimumCombinationGameList {
     }
 }
 
-fn main() -> Result<(), std::io::Error> {
-    print_title();
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if CommonArgs::wants_help(&args) {
+        print_title();
+        print_usage();
+        return;
+    }
 
-    //let content = fs::read_to_string("src/bin/test_input.txt")?;
-    let content = fs::read_to_string("src/bin/input1.txt")?;
-    let lines: Vec<&str> = content.lines().collect();
+    let options = match CommonArgs::parse_shared(&args) {
+        Ok(options) => options,
+        Err(err) => {
+            eprintln!("error: {}\n", err);
+            print_usage();
+            process::exit(1);
+        }
+    };
+
+    let input = Input::locate(options.input.as_deref(), 2, DEFAULT_INPUT);
+    let content = match input.read() {
+        Ok(content) => content,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            process::exit(1);
+        }
+    };
+
+    if options.format != Format::Text {
+        match common::report::<Games>(&content, 2, 2) {
+            Ok(report) => print!("{}", report.render(options.format)),
+            Err(err) => {
+                eprintln!("error: input {}: {}", input, err);
+                process::exit(1);
+            }
+        }
+        return;
+    }
 
-    let minimum_valid_combination_list = check_games(&lines);
+    if options.verbosity != Verbosity::Quiet {
+        print_title();
+    }
 
-    print_result(&minimum_valid_combination_list);
+    let games = match parse_games_observed(&content, &mut StderrLog::new(options.verbosity)) {
+        Ok(games) => games,
+        Err(err) => {
+            eprintln!("error: input {}: {}", input, err);
+            process::exit(1);
+        }
+    };
 
-    Ok(())
+    let minimum_valid_combination_list = check_games(&games);
+
+    print_result(&minimum_valid_combination_list);
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use day02::parse_games;
+    use std::fs;
 
     #[test]
     fn test_solution() {
-        //let content = fs::read_to_string("src/bin/input1.txt").unwrap();
         let content = fs::read_to_string("src/bin/test_input.txt").unwrap();
-        let lines: Vec<&str> = content.lines().collect();
+        let games = parse_games(&content).unwrap();
 
-        let minimum_valid_combination_list = check_games(&lines);
+        let minimum_valid_combination_list = check_games(&games);
 
         assert_eq!(minimum_valid_combination_list.combinations[0].pow(), 48);
         assert_eq!(minimum_valid_combination_list.combinations[1].pow(), 12);
@@ -1,4 +1,4 @@
-use std::error::Error;
+use common::{ParseError, Scanner};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Cube {
@@ -35,25 +35,29 @@ impl Cube {
         }
     }
 
-    pub fn parse_str(line: &str) -> Option<Cube> {
-        let elements: Vec<&str> = line.trim().split_whitespace().collect();
-
-        if elements.len() != 2 {
-            return None;
-        }
-
-        if elements[0].parse::<i32>().is_err() {
-            return None;
+    /// A count followed by a colour, e.g. `3 blue`.
+    pub fn parse(scanner: &mut Scanner) -> Result<Cube, ParseError> {
+        let cube_number = scanner.number::<i32>()?;
+        scanner.spaces()?;
+
+        let checkpoint = scanner.checkpoint();
+        match scanner.word().as_deref() {
+            Ok("red") => Ok(Cube::Red(cube_number)),
+            Ok("green") => Ok(Cube::Green(cube_number)),
+            Ok("blue") => Ok(Cube::Blue(cube_number)),
+            _ => {
+                scanner.rewind(checkpoint);
+                Err(scanner.error("red, green or blue"))
+            }
         }
+    }
 
-        let cube_number = elements[0].parse::<i32>().unwrap();
+    pub fn parse_str(line: &str) -> Option<Cube> {
+        let mut scanner = Scanner::new(line.trim());
+        let cube = Cube::parse(&mut scanner).ok()?;
 
-        match elements[1] {
-            "red" => Some(Cube::Red(cube_number)),
-            "green" => Some(Cube::Green(cube_number)),
-            "blue" => Some(Cube::Blue(cube_number)),
-            _ => None,
-        }
+        scanner.end().ok()?;
+        Some(cube)
     }
 }
 
@@ -118,11 +122,22 @@ mod tests {
         assert!(!control_cube.contains(Cube::Green(45)));
     }
 
+    #[test]
+    fn test_cube_parse_error() {
+        let mut scanner = Scanner::new("4 yellow");
+        let error = Cube::parse(&mut scanner).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 3: expected red, green or blue, found 'y'"
+        );
+    }
+
     #[test]
     #[should_panic]
     fn test_bad_cube_str() {
-        let cube_from_str = Cube::parse_str("4 ygreen").unwrap();
-        let cube_from_str = Cube::parse_str("yigi4 green").unwrap();
-        let cube_from_str = Cube::parse_str("4green").unwrap();
+        let _cube_from_str = Cube::parse_str("4 ygreen").unwrap();
+        let _cube_from_str = Cube::parse_str("yigi4 green").unwrap();
+        let _cube_from_str = Cube::parse_str("4green").unwrap();
     }
 }
@@ -1,4 +1,4 @@
-use std::result;
+use common::{Json, Level, Observer, ParseError, Scanner, Silent, Snapshot, Solution, Table};
 
 use super::*;
 
@@ -27,59 +27,50 @@ impl Game {
     }
 
     pub fn parse_id(line: &str) -> Option<i32> {
-        //
-        let elements: Vec<&str> = line.trim().split_whitespace().collect();
+        let mut scanner = Scanner::new(line.trim());
 
-        if elements.len() != 2 {
-            return None;
-        }
-
-        let parsed_id = elements[1].parse::<i32>();
-        if parsed_id.is_err() {
-            return None;
-        }
+        scanner.word().ok()?;
+        scanner.spaces().ok()?;
+        let id = scanner.number().ok()?;
+        scanner.end().ok()?;
 
-        Some(parsed_id.unwrap())
+        Some(id)
     }
 
-    pub fn parse_subset_list(line: &str) -> Vec<Subset> {
-        //
-        let elements: Vec<&str> = line.trim().split(";").collect();
-        let mut subset_list: Vec<Subset> = Vec::new();
-
-        for elem in elements {
-            let subset_str = elem.trim();
-            match Subset::parse_str(subset_str) {
-                Some(subset) => subset_list.push(subset),
-                None => continue,
-            }
-        }
+    pub fn parse_subset_list(line: &str) -> Result<Vec<Subset>, ParseError> {
+        let mut scanner = Scanner::new(line.trim());
+        let subset_list = scanner.separated_by(";", Subset::parse)?;
 
-        subset_list
+        scanner.end()?;
+        Ok(subset_list)
     }
 
-    pub fn parse_str(line: &str) -> Option<Game> {
-        //
-        let elements: Vec<&str> = line.trim().split(":").collect();
-
-        if elements.len() != 2 {
-            return None;
-        }
+    /// A whole game line, e.g. `Game 1: 3 blue, 4 red; 1 red, 2 green`.
+    pub fn parse(scanner: &mut Scanner) -> Result<Game, ParseError> {
+        scanner.skip_spaces();
+        scanner.word()?;
+        scanner.spaces()?;
+        let new_id = scanner.number()?;
+        scanner.literal(":")?;
+        scanner.skip_spaces();
 
-        let new_id = Game::parse_id(elements[0]);
-
-        if new_id.is_none() {
-            return None;
-        }
+        let new_subset_list = scanner.separated_by(";", Subset::parse)?;
+        scanner.skip_spaces();
 
-        let new_subset_list = Game::parse_subset_list(elements[1]);
-
-        Some(Game {
-            id: new_id.unwrap(),
+        Ok(Game {
+            id: new_id,
             subsets: new_subset_list,
         })
     }
 
+    pub fn parse_str(line: &str) -> Result<Game, ParseError> {
+        let mut scanner = Scanner::new(line);
+        let game = Game::parse(&mut scanner)?;
+
+        scanner.end()?;
+        Ok(game)
+    }
+
     pub fn is_valid_for_subset(&self, subset: &Subset) -> bool {
         for current_subset in self.subsets.iter() {
             if !subset.contains(current_subset) {
@@ -117,6 +108,122 @@ impl Game {
     }
 }
 
+/// One game per line, blank lines are skipped.
+///
+/// Errors point at the line and column of the whole content.
+pub fn parse_games(content: &str) -> Result<Vec<Game>, ParseError> {
+    parse_games_observed(content, &mut Silent)
+}
+
+/// `parse_games`, reporting every game and the total to `observer`.
+pub fn parse_games_observed(
+    content: &str,
+    observer: &mut dyn Observer,
+) -> Result<Vec<Game>, ParseError> {
+    let mut scanner = Scanner::new(content);
+    let mut games: Vec<Game> = Vec::new();
+
+    while !scanner.is_done() {
+        scanner.skip_spaces();
+
+        if scanner.end_of_line().is_ok() {
+            continue;
+        }
+
+        let game = Game::parse(&mut scanner)?;
+        observer.event(
+            Level::Debug,
+            format_args!("game {}: {} subsets", game.id, game.subsets.len()),
+        );
+        games.push(game);
+        scanner.end_of_line()?;
+    }
+
+    observer.event(
+        Level::Info,
+        format_args!("total games parsed: {}", games.len()),
+    );
+    Ok(games)
+}
+
+/// Every game of the puzzle input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Games(pub Vec<Game>);
+
+/// One line per game in the puzzle syntax, every subset listing its three colours.
+impl Snapshot for Games {
+    fn snapshot(&self) -> String {
+        self.0
+            .iter()
+            .map(|game| {
+                let subsets: Vec<String> = game
+                    .subsets
+                    .iter()
+                    .map(|subset| {
+                        format!(
+                            "{} red, {} green, {} blue",
+                            subset.red.count(),
+                            subset.green.count(),
+                            subset.blue.count()
+                        )
+                    })
+                    .collect();
+
+                format!("Game {}: {}\n", game.id, subsets.join("; "))
+            })
+            .collect()
+    }
+}
+
+impl Solution for Games {
+    type PartOne = i32;
+    type PartTwo = i32;
+    type Error = ParseError;
+
+    fn parse(input: &str) -> Result<Self, Self::Error> {
+        parse_games(input).map(Games)
+    }
+
+    fn parse_observed(input: &str, observer: &mut dyn Observer) -> Result<Self, Self::Error> {
+        parse_games_observed(input, observer).map(Games)
+    }
+
+    /// Sum of the ids of the games possible with the puzzle bag.
+    fn part_one(&self) -> i32 {
+        BagReport::check(&default_bag(), &self.0).id_sum
+    }
+
+    /// Sum of the powers of the smallest bag each game needs.
+    fn part_two(&self) -> i32 {
+        self.0
+            .iter()
+            .map(|game| game.minimum_valid_combination().pow())
+            .sum()
+    }
+
+    /// The games checked against the puzzle bag for part one, the smallest
+    /// bag of every game for part two.
+    fn details(&self, part: u32) -> Table {
+        if part == 1 {
+            return bag_table(&[BagReport::check(&default_bag(), &self.0)], &self.0);
+        }
+
+        let mut table = Table::new("games", &["id", "red", "green", "blue", "power"]);
+        for game in &self.0 {
+            let minimum = game.minimum_valid_combination();
+            table.push(vec![
+                Json::from(game.id as i64),
+                Json::from(minimum.red.count() as i64),
+                Json::from(minimum.green.count() as i64),
+                Json::from(minimum.blue.count() as i64),
+                Json::from(minimum.pow() as i64),
+            ]);
+        }
+
+        table
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -154,6 +261,34 @@ mod tests {
         assert!(control_game.is_valid_for_subset(&reference_subset));
     }
 
+    #[test]
+    fn test_games_snapshot() {
+        let content = std::fs::read_to_string("src/bin/test_input.txt").unwrap();
+
+        common::assert_snapshot(
+            "src/snapshots/test_input_games.snap",
+            &Games(parse_games(&content).unwrap()),
+        );
+    }
+
+    #[test]
+    fn test_parse_games_observed() {
+        let mut recorder = common::Recorder::default();
+        let games =
+            parse_games_observed("Game 1: 3 blue; 1 red\n\nGame 2: 2 green\n", &mut recorder)
+                .unwrap();
+
+        assert_eq!(games.len(), 2);
+        assert_eq!(
+            recorder.messages(Level::Debug),
+            vec!["game 1: 2 subsets", "game 2: 1 subsets"]
+        );
+        assert_eq!(
+            recorder.messages(Level::Info),
+            vec!["total games parsed: 2"]
+        );
+    }
+
     #[test]
     fn test_parse_id() {
         let control_id = 69;
@@ -181,11 +316,56 @@ mod tests {
         assert_eq!(
             Game::parse_subset_list(
                 "23 red, 5 green, 7 blue; 69 green, 420 blue; 96 red, 1080 blue"
-            ),
+            )
+            .unwrap(),
             vec![test_subset1, test_subset2, test_subset3]
         )
     }
 
+    #[test]
+    fn test_parse_games() {
+        let games = parse_games("Game 1: 3 blue, 4 red\n\nGame 2: 1 green; 2 blue\n").unwrap();
+
+        assert_eq!(games.len(), 2);
+        assert_eq!(games[1].id, 2);
+        assert_eq!(games[1].subsets.len(), 2);
+
+        let error = parse_games("Game 1: 3 blue\nGame 2: 1 green, 2 purple").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 20: expected red, green or blue, found 'p'"
+        );
+
+        let error = parse_games("Game 1 3 blue").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 7: expected \":\", found ' '"
+        );
+    }
+
+    #[test]
+    fn test_games_solution() {
+        let content = std::fs::read_to_string("src/bin/test_input.txt").unwrap();
+        let games = Games::parse(&content).unwrap();
+
+        assert_eq!(games.0.len(), 5);
+        assert_eq!(games.part_one(), 8);
+        assert_eq!(games.part_two(), 2286);
+
+        let valid: Vec<Json> = games
+            .details(1)
+            .rows
+            .into_iter()
+            .filter(|row| row[3] == Json::from(true))
+            .map(|row| row[2].clone())
+            .collect();
+        assert_eq!(valid, [1i64, 2, 5].map(Json::from).to_vec());
+        assert_eq!(
+            games.details(2).rows[0],
+            [1i64, 4, 2, 6, 48].map(Json::from).to_vec()
+        );
+    }
+
     #[test]
     fn test_minimum_valid_combination() {
         let mut control_subset = Subset::new();
@@ -1,7 +1,9 @@
+mod bag;
 mod cube;
 mod game;
 mod subset;
 
+pub use bag::*;
 pub use cube::*;
 pub use game::*;
 pub use subset::*;
@@ -1,4 +1,4 @@
-use std::error::Error;
+use common::{ParseError, Scanner};
 
 use super::*;
 
@@ -9,6 +9,12 @@ pub struct Subset {
     pub blue: Cube,
 }
 
+impl Default for Subset {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Subset {
     pub fn new() -> Subset {
         Subset {
@@ -46,27 +52,23 @@ impl Subset {
         true
     }
 
-    pub fn parse_str(line: &str) -> Option<Subset> {
-        //
-        let elements: Vec<&str> = line.trim().split(",").collect();
-
-        if elements.len() < 1 {
-            return None;
-        }
-
+    /// Comma separated cubes, e.g. `3 blue, 4 red`.
+    pub fn parse(scanner: &mut Scanner) -> Result<Subset, ParseError> {
         let mut new_subset = Subset::new();
 
-        for elem in elements.iter() {
-            let parsed_subset = Cube::parse_str(elem.trim());
+        for cube in scanner.separated_by(",", Cube::parse)? {
+            new_subset.add(cube);
+        }
 
-            if parsed_subset.is_none() {
-                continue;
-            }
+        Ok(new_subset)
+    }
 
-            new_subset.add(parsed_subset.unwrap());
-        }
+    pub fn parse_str(line: &str) -> Option<Subset> {
+        let mut scanner = Scanner::new(line.trim());
+        let subset = Subset::parse(&mut scanner).ok()?;
 
-        Some(new_subset)
+        scanner.end().ok()?;
+        Some(subset)
     }
 }
 
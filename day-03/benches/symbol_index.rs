@@ -0,0 +1,118 @@
+//! Times symbol adjacency on a generated schema.
+//!
+//! The side of the square grid defaults to 2,000 cells and can be changed
+//! with `SCHEMA_BENCH_SIZE`, e.g. `SCHEMA_BENCH_SIZE=5000 cargo bench`. The
+//! schema keeps a `usize` per cell in its index, so memory grows with the
+//! square of the side: about 800 MB for the index alone at 10,000.
+
+use std::env;
+use std::time::{Duration, Instant};
+
+use day03::{Position, Schema};
+
+const DEFAULT_SIZE: usize = 2_000;
+
+/// Roughly the density of the puzzle input: 2% symbols, 10% digits in runs of
+/// at most three, the rest dots. Seeded so every run sees the same grid.
+fn generate(size: usize) -> String {
+    let mut seed: u64 = 0x2545_f491_4f6c_dd1d;
+    let mut content = String::with_capacity(size * (size + 1));
+
+    for _ in 0..size {
+        let mut run = 0;
+
+        for _ in 0..size {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+
+            let roll = seed % 100;
+            let cell = if roll < 2 {
+                run = 0;
+                '*'
+            } else if roll < 12 && run < 3 {
+                run += 1;
+                char::from(b'0' + (seed / 100 % 10) as u8)
+            } else {
+                run = 0;
+                '.'
+            };
+
+            content.push(cell);
+        }
+
+        content.push('\n');
+    }
+
+    content
+}
+
+fn time<T>(label: &str, task: impl FnOnce() -> T) -> T {
+    let start = Instant::now();
+    let result = task();
+    report(label, start.elapsed());
+
+    result
+}
+
+fn report(label: &str, elapsed: Duration) {
+    println!("{:<32} {:>12.3?}", label, elapsed);
+}
+
+fn main() {
+    let size = env::var("SCHEMA_BENCH_SIZE")
+        .ok()
+        .and_then(|size| size.parse().ok())
+        .unwrap_or(DEFAULT_SIZE);
+
+    println!("schema of {size}x{size} cells");
+
+    let content = time("generate", || generate(size));
+    let schema: Schema = time("parse (with index)", || content.parse().unwrap());
+    drop(content);
+
+    // Parsing already built the symbol index, time the one the schema holds.
+    let symbol_index = schema.symbol_index();
+
+    let parts = time("parts via index", || {
+        (0..schema.elements().len())
+            .filter(|element_index| symbol_index.is_part(*element_index))
+            .count()
+    });
+
+    let probed = time("parts via neighbour probing", || {
+        schema
+            .numbers()
+            .filter(|number| {
+                schema
+                    .adjacent(number)
+                    .iter()
+                    .any(|neighbour| neighbour.is_symbol())
+            })
+            .count()
+    });
+    assert_eq!(parts, probed);
+
+    let touched = time("symbols touching every number", || {
+        schema
+            .numbers()
+            .map(|number| schema.symbols_touching(number).len())
+            .sum::<usize>()
+    });
+
+    let cells = time("touches_symbol on every cell", || {
+        (0..size)
+            .flat_map(|y| (0..size).map(move |x| Position { x, y }))
+            .filter(|position| symbol_index.touches_symbol(*position))
+            .count()
+    });
+
+    println!(
+        "{} elements, {} parts, {} gears, {} number-symbol links, {} cells next to a symbol",
+        schema.elements().len(),
+        parts,
+        schema.gears().len(),
+        touched,
+        cells
+    );
+}
@@ -1,51 +1,155 @@
-use std::fs;
+use std::{env, process};
 
-use day03::{Element, ElementProps, Engine, EnginePart, Position, Schema};
+use common::{CommonArgs, Format, Input, StderrLog, Verbosity};
+use day03::{Engine, Position, RenderStyle, Renderer, Schema};
+
+const DEFAULT_INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/bin/input1.txt");
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct RenderOptions {
+    style: RenderStyle,
+    region: Option<(Position, usize)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Options {
+    render: Option<RenderOptions>,
+    common: CommonArgs,
+}
 
 fn print_title() {
-    println!("Advent of Code 2023 - Day 02 [PART 1]\n");
+    println!("Advent of Code 2023 - Day 03 [PART 1]\n");
     println!("coded with 💛️💙️ by tanque");
     println!("---------------------------------------");
 }
 
-fn main() -> Result<(), std::io::Error> {
-    print_title();
+fn print_usage() {
+    println!("usage: day03-part1 [-v|-q] [--render] [--brackets] [--around <x> <y> <radius>] [--format json|csv|text] [input]\n");
+    println!("  input                        puzzle input file, '-' for stdin (defaults to the committed input)");
+    println!("  --render                     print the schema highlighting parts, numbers, symbols and gears");
+    println!("  --brackets                   use bracket markers instead of ANSI colours");
+    println!("  --around <x> <y> <radius>    only print the cells around a position");
+    println!("  --format json|csv|text       print the answer and every part with its position");
+    println!("  -v, --verbose                also print per-record diagnostics to stderr");
+    println!("  -q, --quiet                  print no diagnostics and no title");
+}
+
+fn parse_usize(value: Option<&String>, name: &str) -> Result<usize, String> {
+    match value {
+        Some(value) => value
+            .parse()
+            .map_err(|_| format!("invalid value '{}' for {}", value, name)),
+        None => Err(format!("missing value for {}", name)),
+    }
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut render = false;
+    let mut options = RenderOptions {
+        style: RenderStyle::Ansi,
+        region: None,
+    };
 
-    // let source_file = "src/bin/input1.txt";
-    let source_file = "src/bin/test_input.txt";
+    let common = CommonArgs::parse(args, |flag, rest| {
+        match flag {
+            "--render" => render = true,
+            "--brackets" => options.style = RenderStyle::Brackets,
+            "--around" => {
+                let x = parse_usize(rest.next(), "x")?;
+                let y = parse_usize(rest.next(), "y")?;
+                let radius = parse_usize(rest.next(), "radius")?;
+                options.region = Some((Position { x, y }, radius));
+                render = true;
+            }
+            _ => return Ok(false),
+        }
 
-    let mut test_schema = Schema::from_file(source_file);
-    println!("\nparsing schema from file \"{}\"...\n", source_file);
+        Ok(true)
+    })?;
+
+    Ok(Options {
+        render: render.then_some(options),
+        common,
+    })
+}
+
+fn print_schema(schema: &Schema, options: &RenderOptions) {
+    let mut renderer = Renderer::new(schema).style(options.style);
+    if let Some((position, radius)) = options.region {
+        renderer = renderer.around(position, radius);
+    }
+
+    println!("\n# SCHEMA #");
+    println!("-----------------\n");
+    println!("{}", renderer.render());
     println!("-----------------");
-    //println!("test_schema is: {:#?}", test_schema);
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if CommonArgs::wants_help(&args) {
+        print_title();
+        print_usage();
+        return;
+    }
+
+    let options = match parse_args(&args) {
+        Ok(options) => options,
+        Err(err) => {
+            eprintln!("error: {}\n", err);
+            print_usage();
+            process::exit(1);
+        }
+    };
+
+    let input = Input::locate(options.common.input.as_deref(), 3, DEFAULT_INPUT);
+    let content = match input.read() {
+        Ok(content) => content,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            process::exit(1);
+        }
+    };
+
+    let engine =
+        match Engine::parse_observed(&content, &mut StderrLog::new(options.common.verbosity)) {
+            Ok(engine) => engine,
+            Err(err) => {
+                eprintln!("error: input {}: {}", input, err);
+                process::exit(1);
+            }
+        };
+
+    if options.common.format != Format::Text {
+        match engine.report_part(1) {
+            Ok(report) => print!("{}", report.render(options.common.format)),
+            Err(err) => {
+                eprintln!("error: {}", err);
+                process::exit(1);
+            }
+        }
+        return;
+    }
+
+    if options.common.verbosity != Verbosity::Quiet {
+        print_title();
+    }
+    println!("\nparsing schema {}...\n", input);
     println!("-----------------");
 
     println!("\n\n# STATISTICS #");
     println!("-----------------\n");
 
-    let parsed_schema = test_schema.schema();
-    let schema_width = parsed_schema.len();
-    let schema_height = parsed_schema[schema_width - 1].len();
-
-    let parsed_engine_parts = test_schema.parts();
-    let mut engine_parts_sum = 0;
-
-    for part in parsed_engine_parts {
-        println!("part: {:?}", part);
-        println!("part value: {}", part.value());
-        engine_parts_sum += part.value();
+    for part in engine.parts() {
+        println!("part {} at {:?}", part.value(), part.position());
     }
 
-    println!("engine schema width: {}", schema_width);
-    println!("engine schema height: {}", schema_height);
-    println!(
-        "total engine parts on schema: {}",
-        parsed_engine_parts.len()
-    );
-    println!("\nsum of engine parts values: {}", engine_parts_sum);
+    println!("\n{}", engine.report());
     println!("-----------------");
 
-    Ok(())
+    if let Some(render_options) = options.render {
+        print_schema(engine.schema(), &render_options);
+    }
 }
 
 #[cfg(test)]
@@ -54,7 +158,58 @@ mod tests {
 
     #[test]
     fn test_solution() {
-        let mut test_schema = Schema::from_file("src/bin/test_input.txt");
-        //
+        let engine = Engine::load("src/bin/test_input_ORIGINAL.txt").unwrap();
+        assert_eq!(engine.part_sum(), 4361);
+
+        let engine = Engine::load("src/bin/test_input.txt").unwrap();
+        assert_eq!(engine.part_sum(), 4561);
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(
+            parse_args(&[]).unwrap(),
+            Options {
+                render: None,
+                common: CommonArgs::default(),
+            }
+        );
+
+        let args: Vec<String> = vec!["--render".to_string(), "--brackets".to_string()];
+        assert_eq!(
+            parse_args(&args).unwrap().render,
+            Some(RenderOptions {
+                style: RenderStyle::Brackets,
+                region: None,
+            })
+        );
+
+        let args: Vec<String> = ["--around", "3", "1", "2"]
+            .iter()
+            .map(|arg| arg.to_string())
+            .collect();
+        assert_eq!(
+            parse_args(&args).unwrap().render,
+            Some(RenderOptions {
+                style: RenderStyle::Ansi,
+                region: Some((Position { x: 3, y: 1 }, 2)),
+            })
+        );
+
+        assert!(parse_args(&["--around".to_string(), "3".to_string()]).is_err());
+        assert!(parse_args(&["--colour".to_string()]).is_err());
+
+        let args: Vec<String> = vec!["--format".to_string(), "json".to_string()];
+        assert_eq!(parse_args(&args).unwrap().common.format, Format::Json);
+        assert!(parse_args(&["--format".to_string()]).is_err());
+
+        let args: Vec<String> = vec!["--render".to_string(), "-".to_string()];
+        assert_eq!(
+            parse_args(&args).unwrap().common.input,
+            Some("-".to_string())
+        );
+
+        let args: Vec<String> = vec!["input.txt".to_string(), "other.txt".to_string()];
+        assert!(parse_args(&args).is_err());
     }
 }
@@ -1,21 +1,93 @@
-use std::fs;
+use std::{env, process};
 
-use day03::{Engine, Schema};
+use common::{CommonArgs, Format, Input, StderrLog, Verbosity};
+use day03::Engine;
+
+const DEFAULT_INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/bin/input1.txt");
+
+fn print_usage() {
+    println!("usage: day03-part2 [-v|-q] [--format json|csv|text] [input]\n");
+    println!(
+        "  input                      puzzle input file, '-' for stdin (defaults to the committed input)"
+    );
+    println!("  --format json|csv|text     print the answer and every gear with its parts");
+    println!("  -v, --verbose              also print per-record diagnostics to stderr");
+    println!("  -q, --quiet                print no diagnostics and no title");
+}
 
 fn print_title() {
-    println!("Advent of Code 2023 - Day 02 [PART 1]\n");
+    println!("Advent of Code 2023 - Day 03 [PART 2]\n");
     println!("coded with 💛️💙️ by tanque");
     println!("---------------------------------------");
 }
 
-fn main() -> Result<(), std::io::Error> {
-    print_title();
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if CommonArgs::wants_help(&args) {
+        print_title();
+        print_usage();
+        return;
+    }
+
+    let options = match CommonArgs::parse_shared(&args) {
+        Ok(options) => options,
+        Err(err) => {
+            eprintln!("error: {}\n", err);
+            print_usage();
+            process::exit(1);
+        }
+    };
+
+    let input = Input::locate(options.input.as_deref(), 3, DEFAULT_INPUT);
+    let content = match input.read() {
+        Ok(content) => content,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            process::exit(1);
+        }
+    };
+
+    let engine = match Engine::parse_observed(&content, &mut StderrLog::new(options.verbosity)) {
+        Ok(engine) => engine,
+        Err(err) => {
+            eprintln!("error: input {}: {}", input, err);
+            process::exit(1);
+        }
+    };
 
-    let content = fs::read_to_string("src/bin/input1.txt")?;
-    //let content = fs::read_to_string("src/bin/test_input.txt")?;
-    let lines: Vec<&str> = content.lines().collect();
+    if options.format != Format::Text {
+        match engine.report_part(2) {
+            Ok(report) => print!("{}", report.render(options.format)),
+            Err(err) => {
+                eprintln!("error: {}", err);
+                process::exit(1);
+            }
+        }
+        return;
+    }
+
+    if options.verbosity != Verbosity::Quiet {
+        print_title();
+    }
+    println!("\nparsing schema {}...\n", input);
+    println!("-----------------");
+
+    println!("\n\n# STATISTICS #");
+    println!("-----------------\n");
+
+    for gear in engine.gears() {
+        println!(
+            "gear at {:?}: {} * {} = {}",
+            gear.symbol.position(),
+            gear.parts.0.value(),
+            gear.parts.1.value(),
+            gear.ratio()
+        );
+    }
 
-    Ok(())
+    println!("total gears on schema: {}", engine.gears().len());
+    println!("\nsum of gear ratios: {}", engine.ratio_sum());
+    println!("-----------------");
 }
 
 #[cfg(test)]
@@ -24,11 +96,10 @@ mod tests {
 
     #[test]
     fn test_solution() {
-        //let content = fs::read_to_string("src/bin/input1.txt").unwrap();
-        let content = fs::read_to_string("src/bin/test_input.txt").unwrap();
-        let lines: Vec<&str> = content.lines().collect();
+        let engine = Engine::load("src/bin/test_input_ORIGINAL.txt").unwrap();
+        assert_eq!(engine.ratio_sum(), 467835);
 
-        //assert_eq!(games_list.id_sum, 2563);
-        assert_eq!(3, 8);
+        let engine = Engine::load("src/bin/test_input.txt").unwrap();
+        assert_eq!(engine.ratio_sum(), 527635);
     }
 }
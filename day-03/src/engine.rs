@@ -1,6 +1,193 @@
-use super::schema::Schema;
+use std::{fmt, io::Read, str::FromStr};
 
+use common::{Observer, Report, Solution};
+
+use super::schema::{Component, DefaultClassifier, EnginePart, Gear, Schema, SchemaError};
+
+/// Figures of a schema, printed one per line.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct EngineReport {
+    pub width: usize,
+    pub height: usize,
+    pub numbers: usize,
+    pub parts: usize,
+    pub part_sum: u64,
+    pub symbols: usize,
+    pub gears: usize,
+    pub ratio_sum: u64,
+}
+
+impl fmt::Display for EngineReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "engine schema width: {}", self.width)?;
+        writeln!(f, "engine schema height: {}", self.height)?;
+        writeln!(f, "numbers on schema: {}", self.numbers)?;
+        writeln!(f, "symbols on schema: {}", self.symbols)?;
+        writeln!(f, "total engine parts on schema: {}", self.parts)?;
+        writeln!(f, "sum of engine parts values: {}", self.part_sum)?;
+        writeln!(f, "total gears on schema: {}", self.gears)?;
+        write!(f, "sum of gear ratios: {}", self.ratio_sum)
+    }
+}
+
+/// Entry point to the puzzle: a loaded schema and the answers computed from it.
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Engine {
     schema: Schema,
 }
+
+impl FromStr for Engine {
+    type Err = SchemaError;
+
+    fn from_str(content: &str) -> Result<Self, Self::Err> {
+        content.parse().map(Engine::new)
+    }
+}
+
+impl Engine {
+    pub fn new(schema: Schema) -> Engine {
+        Engine { schema }
+    }
+
+    pub fn load(file_path: &str) -> Result<Engine, SchemaError> {
+        Schema::from_file(file_path).map(Engine::new)
+    }
+
+    /// Parses `content`, reporting what the schema holds to `observer`.
+    pub fn parse_observed(
+        content: &str,
+        observer: &mut dyn Observer,
+    ) -> Result<Engine, SchemaError> {
+        Schema::from_str_observed(content, &DefaultClassifier, observer).map(Engine::new)
+    }
+
+    pub fn from_reader(reader: impl Read) -> Result<Engine, SchemaError> {
+        Schema::from_reader(reader).map(Engine::new)
+    }
+
+    pub fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    pub fn parts(&self) -> &[EnginePart] {
+        self.schema.parts()
+    }
+
+    pub fn part_sum(&self) -> u64 {
+        self.schema.part_one()
+    }
+
+    pub fn gears(&self) -> &[Gear] {
+        self.schema.gears()
+    }
+
+    pub fn components(&self) -> Vec<Component<'_>> {
+        self.schema.components()
+    }
+
+    pub fn ratios(&self) -> Vec<u64> {
+        self.gears().iter().map(|gear| gear.ratio()).collect()
+    }
+
+    pub fn ratio_sum(&self) -> u64 {
+        self.schema.part_two()
+    }
+
+    /// The answer to `part` with the parts or gears behind it.
+    pub fn report_part(&self, part: u32) -> Result<Report, String> {
+        let answer = match part {
+            1 => self.part_sum().to_string(),
+            2 => self.ratio_sum().to_string(),
+            _ => return Err(format!("no part {}, parts are 1 and 2", part)),
+        };
+
+        Ok(Report {
+            day: 3,
+            part,
+            answer,
+            details: self.schema.details(part),
+        })
+    }
+
+    pub fn report(&self) -> EngineReport {
+        EngineReport {
+            width: self.schema.width(),
+            height: self.schema.height(),
+            numbers: self.schema.numbers().count(),
+            parts: self.parts().len(),
+            part_sum: self.part_sum(),
+            symbols: self.schema.part_graph().symbols().count(),
+            gears: self.gears().len(),
+            ratio_sum: self.ratio_sum(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_engine() {
+        let engine = Engine::load("src/bin/test_input_ORIGINAL.txt").unwrap();
+
+        assert_eq!(engine.parts().len(), 8);
+        assert_eq!(engine.part_sum(), 4361);
+        assert_eq!(engine.ratios(), vec![16345, 451490]);
+        assert_eq!(engine.ratio_sum(), 467835);
+
+        let components = engine.components();
+        assert_eq!(components.len(), 6);
+        assert_eq!(
+            components
+                .iter()
+                .map(|component| component.value())
+                .sum::<u64>(),
+            engine.part_sum()
+        );
+
+        assert!(Engine::load("src/bin/missing.txt").is_err());
+
+        let report = engine.report_part(2).unwrap();
+        assert_eq!(report.answer, "467835");
+        assert_eq!(report.details.name, "gears");
+        assert_eq!(report.details.rows.len(), 2);
+
+        assert_eq!(
+            engine.report_part(7),
+            Err("no part 7, parts are 1 and 2".to_string())
+        );
+    }
+
+    #[test]
+    fn test_report() {
+        let engine: Engine = "467..114..\n...*......\n..35..633.\n......#..."
+            .parse()
+            .unwrap();
+
+        assert_eq!(
+            engine.report(),
+            EngineReport {
+                width: 10,
+                height: 4,
+                numbers: 4,
+                parts: 3,
+                part_sum: 1135,
+                symbols: 2,
+                gears: 1,
+                ratio_sum: 16345,
+            }
+        );
+        assert_eq!(
+            engine.report().to_string(),
+            "engine schema width: 10\n\
+             engine schema height: 4\n\
+             numbers on schema: 4\n\
+             symbols on schema: 2\n\
+             total engine parts on schema: 3\n\
+             sum of engine parts values: 1135\n\
+             total gears on schema: 1\n\
+             sum of gear ratios: 16345"
+        );
+    }
+}
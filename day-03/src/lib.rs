@@ -1,7 +1,5 @@
 mod engine;
-mod scanner;
 mod schema;
 
 pub use engine::*;
-pub use scanner::*;
 pub use schema::*;
@@ -0,0 +1,132 @@
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum CellKind {
+    Blank,
+    Digit,
+    Symbol,
+    Ignored,
+}
+
+pub trait CellClassifier {
+    fn classify(&self, character: char) -> CellKind;
+}
+
+impl<F> CellClassifier for F
+where
+    F: Fn(char) -> CellKind,
+{
+    fn classify(&self, character: char) -> CellKind {
+        self(character)
+    }
+}
+
+/// `.` is blank, letters are ignored, ASCII digits are digits and anything else is a symbol.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct DefaultClassifier;
+
+impl CellClassifier for DefaultClassifier {
+    fn classify(&self, character: char) -> CellKind {
+        if character.eq(&'.') {
+            CellKind::Blank
+        } else if character.is_alphabetic() {
+            CellKind::Ignored
+        } else if character.is_ascii_digit() {
+            CellKind::Digit
+        } else {
+            CellKind::Symbol
+        }
+    }
+}
+
+/// Explicit per-character table.
+///
+/// Characters not in the table are digits when ASCII digits and `fallback` otherwise.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TableClassifier {
+    table: HashMap<char, CellKind>,
+    fallback: CellKind,
+}
+
+impl TableClassifier {
+    pub fn new(fallback: CellKind) -> TableClassifier {
+        TableClassifier {
+            table: HashMap::new(),
+            fallback,
+        }
+    }
+
+    pub fn with(mut self, characters: &str, kind: CellKind) -> TableClassifier {
+        for character in characters.chars() {
+            self.table.insert(character, kind);
+        }
+        self
+    }
+
+    pub fn blanks(self, characters: &str) -> TableClassifier {
+        self.with(characters, CellKind::Blank)
+    }
+
+    pub fn symbols(self, characters: &str) -> TableClassifier {
+        self.with(characters, CellKind::Symbol)
+    }
+
+    pub fn ignored(self, characters: &str) -> TableClassifier {
+        self.with(characters, CellKind::Ignored)
+    }
+}
+
+impl CellClassifier for TableClassifier {
+    fn classify(&self, character: char) -> CellKind {
+        match self.table.get(&character) {
+            Some(kind) => *kind,
+            None if character.is_ascii_digit() => CellKind::Digit,
+            None => self.fallback,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_classifier() {
+        let classifier = DefaultClassifier;
+
+        assert_eq!(classifier.classify('.'), CellKind::Blank);
+        assert_eq!(classifier.classify('a'), CellKind::Ignored);
+        assert_eq!(classifier.classify('7'), CellKind::Digit);
+        assert_eq!(classifier.classify('*'), CellKind::Symbol);
+        assert_eq!(classifier.classify('#'), CellKind::Symbol);
+        assert_eq!(classifier.classify('٣'), CellKind::Symbol);
+    }
+
+    #[test]
+    fn test_table_classifier() {
+        let classifier = TableClassifier::new(CellKind::Symbol)
+            .blanks("#.")
+            .ignored("~")
+            .with("0", CellKind::Blank);
+
+        assert_eq!(classifier.classify('#'), CellKind::Blank);
+        assert_eq!(classifier.classify('.'), CellKind::Blank);
+        assert_eq!(classifier.classify('~'), CellKind::Ignored);
+        assert_eq!(classifier.classify('a'), CellKind::Symbol);
+        assert_eq!(classifier.classify('7'), CellKind::Digit);
+        assert_eq!(classifier.classify('0'), CellKind::Blank);
+    }
+
+    #[test]
+    fn test_closure_classifier() {
+        let classifier = |character: char| match character {
+            '#' => CellKind::Blank,
+            '0'..='9' => CellKind::Digit,
+            _ => CellKind::Symbol,
+        };
+
+        assert_eq!(classifier.classify('#'), CellKind::Blank);
+        assert_eq!(classifier.classify('.'), CellKind::Symbol);
+        assert_eq!(classifier.classify('4'), CellKind::Digit);
+    }
+}
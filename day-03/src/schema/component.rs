@@ -0,0 +1,155 @@
+use super::element::Element;
+use super::position::Position;
+
+/// Parts and symbols reachable from each other through adjacency: an independent
+/// assembly of the schema.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Component<'a> {
+    pub parts: Vec<&'a Element>,
+    pub symbols: Vec<&'a Element>,
+    pub top_left: Position,
+    pub bottom_right: Position,
+}
+
+impl<'a> Component<'a> {
+    /// Groups `elements` into a component, keeping schema order.
+    pub fn from_elements(elements: Vec<&'a Element>) -> Component<'a> {
+        let (parts, symbols): (Vec<&Element>, Vec<&Element>) = elements
+            .into_iter()
+            .partition(|element| element.is_number());
+
+        let mut cells = parts
+            .iter()
+            .chain(symbols.iter())
+            .flat_map(|element| element.cells());
+        let first = cells.next().unwrap_or(Position { x: 0, y: 0 });
+
+        let (top_left, bottom_right) =
+            cells.fold((first, first), |(top_left, bottom_right), cell| {
+                (
+                    Position {
+                        x: top_left.x.min(cell.x),
+                        y: top_left.y.min(cell.y),
+                    },
+                    Position {
+                        x: bottom_right.x.max(cell.x),
+                        y: bottom_right.y.max(cell.y),
+                    },
+                )
+            });
+
+        Component {
+            parts,
+            symbols,
+            top_left,
+            bottom_right,
+        }
+    }
+
+    pub fn value(&self) -> u64 {
+        self.parts
+            .iter()
+            .filter_map(|part| part.number())
+            .map(u64::from)
+            .sum()
+    }
+
+    pub fn width(&self) -> usize {
+        self.bottom_right.x - self.top_left.x + 1
+    }
+
+    pub fn height(&self) -> usize {
+        self.bottom_right.y - self.top_left.y + 1
+    }
+
+    pub fn contains(&self, position: Position) -> bool {
+        (self.top_left.x..=self.bottom_right.x).contains(&position.x)
+            && (self.top_left.y..=self.bottom_right.y).contains(&position.y)
+    }
+}
+
+/// Union-find over element indexes, with path halving and union by size.
+#[derive(Debug, Clone)]
+pub struct DisjointSet {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+}
+
+impl DisjointSet {
+    pub fn new(len: usize) -> DisjointSet {
+        DisjointSet {
+            parent: (0..len).collect(),
+            size: vec![1; len],
+        }
+    }
+
+    pub fn find(&mut self, mut element: usize) -> usize {
+        while self.parent[element] != element {
+            self.parent[element] = self.parent[self.parent[element]];
+            element = self.parent[element];
+        }
+
+        element
+    }
+
+    pub fn union(&mut self, left: usize, right: usize) {
+        let (mut left, mut right) = (self.find(left), self.find(right));
+        if left == right {
+            return;
+        }
+
+        if self.size[left] < self.size[right] {
+            std::mem::swap(&mut left, &mut right);
+        }
+
+        self.parent[right] = left;
+        self.size[left] += self.size[right];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::ElementProps;
+    use super::*;
+
+    #[test]
+    fn test_disjoint_set() {
+        let mut set = DisjointSet::new(5);
+
+        set.union(0, 1);
+        set.union(3, 4);
+        set.union(1, 4);
+
+        assert_eq!(set.find(0), set.find(3));
+        assert_ne!(set.find(0), set.find(2));
+        assert_eq!(set.find(2), 2);
+    }
+
+    #[test]
+    fn test_component_bounds() {
+        let number = Element::Number(
+            ElementProps {
+                position: Position { x: 0, y: 0 },
+                value: "467".to_string(),
+                width: 3,
+            },
+            467,
+        );
+        let symbol = Element::Symbol(ElementProps {
+            position: Position { x: 3, y: 1 },
+            value: "*".to_string(),
+            width: 1,
+        });
+
+        let component = Component::from_elements(vec![&number, &symbol]);
+
+        assert_eq!(component.parts, vec![&number]);
+        assert_eq!(component.symbols, vec![&symbol]);
+        assert_eq!(component.top_left, Position { x: 0, y: 0 });
+        assert_eq!(component.bottom_right, Position { x: 3, y: 1 });
+        assert_eq!((component.width(), component.height()), (4, 2));
+        assert_eq!(component.value(), 467);
+        assert!(component.contains(Position { x: 3, y: 0 }));
+        assert!(!component.contains(Position { x: 4, y: 1 }));
+    }
+}
@@ -11,35 +11,84 @@ pub struct ElementProps {
 pub enum Element {
     Dot(ElementProps),
     Symbol(ElementProps),
-    Number(ElementProps),
+    Number(ElementProps, u32),
     Unknown(ElementProps),
 }
 
 impl Element {
-    pub fn position(&self) -> &Position {
+    pub fn props(&self) -> &ElementProps {
         match self {
-            Element::Dot(props) => &props.position,
-            Element::Symbol(props) => &props.position,
-            Element::Number(props) => &props.position,
-            Element::Unknown(props) => &props.position,
+            Element::Dot(props) => props,
+            Element::Symbol(props) => props,
+            Element::Number(props, _) => props,
+            Element::Unknown(props) => props,
         }
     }
 
+    pub fn position(&self) -> &Position {
+        &self.props().position
+    }
+
     pub fn value(&self) -> &String {
-        match self {
-            Element::Dot(props) => &props.value,
-            Element::Symbol(props) => &props.value,
-            Element::Number(props) => &props.value,
-            Element::Unknown(props) => &props.value,
-        }
+        &self.props().value
     }
 
     pub fn width(&self) -> usize {
+        self.props().width
+    }
+
+    pub fn number(&self) -> Option<u32> {
         match self {
-            Element::Dot(props) => props.width,
-            Element::Symbol(props) => props.width,
-            Element::Number(props) => props.width,
-            Element::Unknown(props) => props.width,
+            Element::Number(_, number) => Some(*number),
+            _ => None,
         }
     }
+
+    pub fn is_number(&self) -> bool {
+        matches!(self, Element::Number(..))
+    }
+
+    pub fn is_symbol(&self) -> bool {
+        matches!(self, Element::Symbol(_))
+    }
+
+    /// Every position covered by the element, from left to right.
+    pub fn cells(&self) -> impl Iterator<Item = Position> {
+        let Position { x, y } = *self.position();
+
+        (x..x + self.width()).map(move |x| Position { x, y })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_number_element() {
+        let number = Element::Number(
+            ElementProps {
+                position: Position { x: 4, y: 2 },
+                value: "467".to_string(),
+                width: 3,
+            },
+            467,
+        );
+
+        assert_eq!(number.number(), Some(467));
+        assert_eq!(number.value(), "467");
+        assert_eq!(number.width(), 3);
+        assert!(number.is_number());
+        assert!(!number.is_symbol());
+
+        let cells: Vec<Position> = number.cells().collect();
+        assert_eq!(
+            cells,
+            vec![
+                Position { x: 4, y: 2 },
+                Position { x: 5, y: 2 },
+                Position { x: 6, y: 2 }
+            ]
+        );
+    }
 }
@@ -1,22 +1,23 @@
 use super::element::Element;
+use super::position::Position;
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct EnginePart {
     //pub label: String,
-    pub elements: Vec<Element>,
+    pub element: Element,
 }
 
 impl EnginePart {
-    pub fn value(&self) -> i32 {
-        //
-        let mut total_value = 0;
+    pub fn value(&self) -> u32 {
+        self.element.number().unwrap_or(0)
+    }
 
-        for element in &self.elements {
-            total_value *= 10;
-            total_value += element.value().parse().unwrap_or(0);
-        }
+    pub fn position(&self) -> &Position {
+        self.element.position()
+    }
 
-        total_value
+    pub fn width(&self) -> usize {
+        self.element.width()
     }
 }
 
@@ -24,30 +25,22 @@ impl EnginePart {
 mod tests {
     use super::super::*;
     use super::*;
-    use std::fs;
 
     #[test]
     fn test_engine_part() {
         //
         let mock_part = EnginePart {
-            elements: vec![
-                Element::Number(ElementProps {
+            element: Element::Number(
+                ElementProps {
                     position: Position { x: 2, y: 2 },
-                    value: "3".to_string(),
-                    width: 1,
-                }),
-                Element::Number(ElementProps {
-                    position: Position { x: 3, y: 2 },
-                    value: "5".to_string(),
-                    width: 1,
-                }),
-                Element::Number(ElementProps {
-                    position: Position { x: 8, y: 5 },
-                    value: "8".to_string(),
-                    width: 1,
-                }),
-            ],
+                    value: "358".to_string(),
+                    width: 3,
+                },
+                358,
+            ),
         };
         assert_eq!(mock_part.value(), 358);
+        assert_eq!(mock_part.position(), &Position { x: 2, y: 2 });
+        assert_eq!(mock_part.width(), 3);
     }
 }
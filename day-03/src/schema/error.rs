@@ -0,0 +1,42 @@
+use std::{error::Error, fmt, io};
+
+use super::position::Position;
+
+#[derive(Debug)]
+pub enum SchemaError {
+    Io(io::Error),
+    InvalidNumber { position: Position, value: String },
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaError::Io(err) => write!(f, "cannot read schema: {}", err),
+            // Positions are 0-based, lines and columns are reported from 1
+            // like the `ParseError`s of the other days.
+            SchemaError::InvalidNumber { position, value } => write!(
+                f,
+                "line {}, column {}: expected a number up to {}, found '{}'",
+                position.y + 1,
+                position.x + 1,
+                u32::MAX,
+                value
+            ),
+        }
+    }
+}
+
+impl Error for SchemaError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SchemaError::Io(err) => Some(err),
+            SchemaError::InvalidNumber { .. } => None,
+        }
+    }
+}
+
+impl From<io::Error> for SchemaError {
+    fn from(err: io::Error) -> Self {
+        SchemaError::Io(err)
+    }
+}
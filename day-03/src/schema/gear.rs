@@ -0,0 +1,53 @@
+use super::element::Element;
+use super::engine_part::EnginePart;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Gear {
+    pub symbol: Element,
+    pub parts: (EnginePart, EnginePart),
+}
+
+impl Gear {
+    pub fn ratio(&self) -> u64 {
+        self.parts.0.value() as u64 * self.parts.1.value() as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+    use super::*;
+
+    fn mock_part(x: usize, y: usize, value: u32) -> EnginePart {
+        EnginePart {
+            element: Element::Number(
+                ElementProps {
+                    position: Position { x, y },
+                    value: value.to_string(),
+                    width: value.to_string().len(),
+                },
+                value,
+            ),
+        }
+    }
+
+    #[test]
+    fn test_gear_ratio() {
+        let gear = Gear {
+            symbol: Element::Symbol(ElementProps {
+                position: Position { x: 3, y: 1 },
+                value: "*".to_string(),
+                width: 1,
+            }),
+            parts: (mock_part(0, 0, 467), mock_part(2, 2, 35)),
+        };
+
+        assert_eq!(gear.ratio(), 16345);
+
+        let large_gear = Gear {
+            parts: (mock_part(0, 0, 99999), mock_part(6, 0, 99999)),
+            ..gear
+        };
+        assert_eq!(large_gear.ratio(), 9_999_800_001);
+    }
+}
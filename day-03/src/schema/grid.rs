@@ -0,0 +1,230 @@
+use super::position::{Direction, Position};
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Connectivity {
+    Four,
+    Eight,
+}
+
+impl Connectivity {
+    pub fn directions(&self) -> &'static [Direction] {
+        match self {
+            Connectivity::Four => &Direction::CARDINAL,
+            Connectivity::Eight => &Direction::ALL,
+        }
+    }
+}
+
+/// Rows of cells addressed by `Position { x: column, y: row }`.
+///
+/// Rows are allowed to have different lengths: `width` is the length of the
+/// longest row and a position past the end of a shorter row is out of bounds.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Grid<T> {
+    rows: Vec<Vec<T>>,
+    width: usize,
+}
+
+impl<T> Default for Grid<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn new() -> Grid<T> {
+        Grid {
+            rows: Vec::new(),
+            width: 0,
+        }
+    }
+
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Grid<T> {
+        let mut grid = Grid::new();
+
+        for row in rows {
+            grid.push_row(row);
+        }
+
+        grid
+    }
+
+    pub fn push_row(&mut self, row: Vec<T>) {
+        self.width = self.width.max(row.len());
+        self.rows.push(row);
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.iter().all(|row| row.is_empty())
+    }
+
+    pub fn contains(&self, position: Position) -> bool {
+        self.get(position).is_some()
+    }
+
+    pub fn get(&self, position: Position) -> Option<&T> {
+        self.rows.get(position.y)?.get(position.x)
+    }
+
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        self.rows.get(y).map(|row| row.as_slice())
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.rows.iter().map(|row| row.as_slice())
+    }
+
+    /// Cells of column `x` from top to bottom, skipping rows too short to reach it.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        self.rows.iter().filter_map(move |row| row.get(x))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |x| self.column(x))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.rows.iter().enumerate().flat_map(|(y, row)| {
+            row.iter()
+                .enumerate()
+                .map(move |(x, cell)| (Position { x, y }, cell))
+        })
+    }
+
+    pub fn neighbours(
+        &self,
+        position: Position,
+        connectivity: Connectivity,
+    ) -> impl Iterator<Item = (Position, &T)> {
+        connectivity
+            .directions()
+            .iter()
+            .filter_map(move |direction| self.cell_at(position, *direction))
+    }
+
+    /// Cells at most `radius` rows and columns away from `position`.
+    pub fn neighbourhood(
+        &self,
+        position: Position,
+        radius: usize,
+    ) -> impl Iterator<Item = (Position, &T)> {
+        Direction::within(radius)
+            .into_iter()
+            .filter_map(move |direction| self.cell_at(position, direction))
+    }
+
+    fn cell_at(&self, position: Position, direction: Direction) -> Option<(Position, &T)> {
+        let neighbour = position.offset(direction)?;
+        self.get(neighbour).map(|cell| (neighbour, cell))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_grid() -> Grid<char> {
+        Grid::from_rows(vec![
+            "467".chars().collect(),
+            "..*..".chars().collect(),
+            "3".chars().collect(),
+        ])
+    }
+
+    #[test]
+    fn test_grid_size() {
+        let grid = test_grid();
+
+        assert_eq!(grid.width(), 5);
+        assert_eq!(grid.height(), 3);
+        assert!(!grid.is_empty());
+        assert!(Grid::<char>::new().is_empty());
+    }
+
+    #[test]
+    fn test_grid_get() {
+        let grid = test_grid();
+
+        assert_eq!(grid.get(Position { x: 0, y: 0 }), Some(&'4'));
+        assert_eq!(grid.get(Position { x: 2, y: 1 }), Some(&'*'));
+        assert_eq!(grid.get(Position { x: 4, y: 1 }), Some(&'.'));
+        assert_eq!(grid.get(Position { x: 4, y: 0 }), None);
+        assert_eq!(grid.get(Position { x: 1, y: 2 }), None);
+        assert_eq!(grid.get(Position { x: 0, y: 3 }), None);
+    }
+
+    #[test]
+    fn test_grid_rows_and_columns() {
+        let grid = test_grid();
+
+        assert_eq!(grid.row(2), Some(&['3'][..]));
+        assert_eq!(grid.row(3), None);
+        assert_eq!(grid.rows().count(), 3);
+
+        let column: Vec<&char> = grid.column(0).collect();
+        assert_eq!(column, vec![&'4', &'.', &'3']);
+
+        let column: Vec<&char> = grid.column(3).collect();
+        assert_eq!(column, vec![&'.']);
+
+        let columns: Vec<usize> = grid.columns().map(|column| column.count()).collect();
+        assert_eq!(columns, vec![3, 2, 2, 1, 1]);
+    }
+
+    #[test]
+    fn test_grid_iter() {
+        let grid = test_grid();
+        let cells: Vec<(Position, &char)> = grid.iter().collect();
+
+        assert_eq!(cells.len(), 9);
+        assert_eq!(cells[3], (Position { x: 0, y: 1 }, &'.'));
+        assert_eq!(cells[8], (Position { x: 0, y: 2 }, &'3'));
+    }
+
+    #[test]
+    fn test_grid_neighbours() {
+        let grid = test_grid();
+
+        let neighbours: Vec<Position> = grid
+            .neighbours(Position { x: 0, y: 0 }, Connectivity::Four)
+            .map(|(position, _)| position)
+            .collect();
+        assert_eq!(
+            neighbours,
+            vec![Position { x: 1, y: 0 }, Position { x: 0, y: 1 }]
+        );
+
+        let neighbours: Vec<&char> = grid
+            .neighbours(Position { x: 1, y: 1 }, Connectivity::Eight)
+            .map(|(_, cell)| cell)
+            .collect();
+        assert_eq!(neighbours, vec![&'4', &'6', &'7', &'.', &'*', &'3']);
+
+        let neighbours: Vec<Position> = grid
+            .neighbours(Position { x: 4, y: 1 }, Connectivity::Eight)
+            .map(|(position, _)| position)
+            .collect();
+        assert_eq!(neighbours, vec![Position { x: 3, y: 1 }]);
+    }
+
+    #[test]
+    fn test_grid_neighbourhood() {
+        let grid = test_grid();
+
+        let neighbourhood: Vec<&char> = grid
+            .neighbourhood(Position { x: 0, y: 0 }, 2)
+            .map(|(_, cell)| cell)
+            .collect();
+        assert_eq!(neighbourhood, vec![&'6', &'7', &'.', &'.', &'*', &'3']);
+
+        assert_eq!(grid.neighbourhood(Position { x: 2, y: 1 }, 0).count(), 0);
+    }
+}
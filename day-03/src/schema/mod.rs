@@ -1,9 +1,28 @@
+mod classifier;
+mod component;
 mod element;
 mod engine_part;
+mod error;
+mod gear;
+mod grid;
+mod part_graph;
 mod position;
+mod render;
+#[allow(clippy::module_inception)]
 mod schema;
+mod symbol_index;
+mod tokenizer;
 
+pub use classifier::{CellClassifier, CellKind, DefaultClassifier, TableClassifier};
+pub use component::Component;
 pub use element::{Element, ElementProps};
 pub use engine_part::EnginePart;
-pub use position::Position;
+pub use error::SchemaError;
+pub use gear::Gear;
+pub use grid::{Connectivity, Grid};
+pub use part_graph::{Attachment, PartGraph};
+pub use position::{Direction, Position};
+pub use render::{Highlight, RenderStyle, Renderer};
 pub use schema::Schema;
+pub use symbol_index::SymbolIndex;
+pub use tokenizer::tokenize_row;
@@ -0,0 +1,290 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use super::component::{Component, DisjointSet};
+use super::element::Element;
+use super::grid::Connectivity;
+use super::position::Position;
+use super::schema::Schema;
+
+/// A symbol together with the parts touching it.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Attachment<'a> {
+    pub symbol: &'a Element,
+    pub parts: Vec<&'a Element>,
+}
+
+impl Attachment<'_> {
+    pub fn values(&self) -> impl Iterator<Item = u32> + '_ {
+        self.parts.iter().filter_map(|part| part.number())
+    }
+
+    pub fn sum(&self) -> u64 {
+        self.values().map(u64::from).sum()
+    }
+
+    /// Product of the attached parts, the gear ratio when there are two of them.
+    pub fn product(&self) -> u64 {
+        self.values().map(u64::from).product()
+    }
+}
+
+/// Bipartite view of a schema: symbols on one side, parts on the other, with
+/// an edge wherever a part touches a symbol.
+#[derive(Debug, Clone, Copy)]
+pub struct PartGraph<'a> {
+    schema: &'a Schema,
+}
+
+impl<'a> PartGraph<'a> {
+    pub fn new(schema: &'a Schema) -> PartGraph<'a> {
+        PartGraph { schema }
+    }
+
+    fn element(&self, element_index: usize) -> &'a Element {
+        &self.schema.elements()[element_index]
+    }
+
+    fn linked(&self, element_indexes: &[usize]) -> Vec<&'a Element> {
+        element_indexes
+            .iter()
+            .map(|element_index| self.element(*element_index))
+            .collect()
+    }
+
+    pub fn symbols(&self) -> impl Iterator<Item = &'a Element> {
+        self.schema
+            .elements()
+            .iter()
+            .filter(|element| element.is_symbol())
+    }
+
+    /// Parts touching `symbol`, in schema order.
+    pub fn parts_of(&self, symbol: &Element) -> Vec<&'a Element> {
+        match self.schema.element_index(*symbol.position()) {
+            Some(element_index) => {
+                self.linked(self.schema.symbol_index().numbers_touching(element_index))
+            }
+            None => Vec::new(),
+        }
+    }
+
+    /// Symbols touching `part`, in schema order.
+    pub fn symbols_of(&self, part: &Element) -> Vec<&'a Element> {
+        match self.schema.element_index(*part.position()) {
+            Some(element_index) => {
+                self.linked(self.schema.symbol_index().symbols_touching(element_index))
+            }
+            None => Vec::new(),
+        }
+    }
+
+    /// Every symbol of the schema with its parts, symbols without parts included.
+    pub fn attachments(&self) -> impl Iterator<Item = Attachment<'a>> + '_ {
+        self.symbols().map(|symbol| Attachment {
+            symbol,
+            parts: self.parts_of(symbol),
+        })
+    }
+
+    /// Symbols spelled `symbol` touching exactly `count` parts.
+    ///
+    /// Gears are `matching("*", 2)`; any other symbol or count follows the same rule.
+    pub fn matching(&self, symbol: &str, count: usize) -> Vec<Attachment<'a>> {
+        self.attachments()
+            .filter(|attachment| attachment.symbol.value() == symbol)
+            .filter(|attachment| attachment.parts.len() == count)
+            .collect()
+    }
+
+    /// Symbol types by the number of distinct parts they connect, most first.
+    ///
+    /// A part touching two symbols of the same type is only counted once for it.
+    pub fn ranking(&self) -> Vec<(&'a str, usize)> {
+        let mut parts_by_type: BTreeMap<&str, HashSet<Position>> = BTreeMap::new();
+
+        for attachment in self.attachments() {
+            parts_by_type
+                .entry(attachment.symbol.value().as_str())
+                .or_default()
+                .extend(attachment.parts.iter().map(|part| *part.position()));
+        }
+
+        let mut ranking: Vec<(&str, usize)> = parts_by_type
+            .into_iter()
+            .map(|(symbol, parts)| (symbol, parts.len()))
+            .collect();
+
+        ranking.sort_by(|left, right| right.1.cmp(&left.1).then(left.0.cmp(right.0)));
+        ranking
+    }
+
+    /// Groups parts and symbols into components, linking every part to the
+    /// symbols it touches and every symbol to its neighbouring symbols.
+    ///
+    /// Numbers that are not parts belong to no component, while a symbol
+    /// without parts forms a component of its own. Components are ordered by
+    /// their first element in the schema.
+    pub fn components(&self) -> Vec<Component<'a>> {
+        let elements = self.schema.elements();
+        let symbol_index = self.schema.symbol_index();
+        let mut set = DisjointSet::new(elements.len());
+
+        for (symbol, element) in elements.iter().enumerate() {
+            if !element.is_symbol() {
+                continue;
+            }
+
+            for number in symbol_index.numbers_touching(symbol) {
+                set.union(symbol, *number);
+            }
+
+            for (_, neighbour) in self
+                .schema
+                .index()
+                .neighbours(*element.position(), Connectivity::Eight)
+            {
+                if elements[*neighbour].is_symbol() {
+                    set.union(symbol, *neighbour);
+                }
+            }
+        }
+
+        let mut slots: HashMap<usize, usize> = HashMap::new();
+        let mut members: Vec<Vec<&Element>> = Vec::new();
+
+        for (element_index, element) in elements.iter().enumerate() {
+            if !element.is_symbol() && !symbol_index.is_part(element_index) {
+                continue;
+            }
+
+            let slot = *slots.entry(set.find(element_index)).or_insert_with(|| {
+                members.push(Vec::new());
+                members.len() - 1
+            });
+            members[slot].push(element);
+        }
+
+        members.into_iter().map(Component::from_elements).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_SCHEMA: &str = "467..114..\n...*......\n..35..633.\n......#...\n617*......\n.....+.58.\n..592.....\n......755.\n...$.*....\n.664.598..";
+
+    fn values(elements: &[&Element]) -> Vec<u32> {
+        elements
+            .iter()
+            .filter_map(|element| element.number())
+            .collect()
+    }
+
+    #[test]
+    fn test_parts_and_symbols() {
+        let testing_schema: Schema = TEST_SCHEMA.parse().unwrap();
+        let graph = PartGraph::new(&testing_schema);
+
+        let star = testing_schema.get(Position { x: 3, y: 1 }).unwrap();
+        assert_eq!(values(&graph.parts_of(star)), vec![467, 35]);
+
+        let plus = testing_schema.get(Position { x: 5, y: 5 }).unwrap();
+        assert_eq!(values(&graph.parts_of(plus)), vec![592]);
+
+        let part = testing_schema.get(Position { x: 6, y: 2 }).unwrap();
+        let symbols: Vec<&String> = graph
+            .symbols_of(part)
+            .iter()
+            .map(|symbol| symbol.value())
+            .collect();
+        assert_eq!(symbols, vec!["#"]);
+
+        let invalid = testing_schema.get(Position { x: 5, y: 0 }).unwrap();
+        assert!(graph.symbols_of(invalid).is_empty());
+
+        assert_eq!(graph.symbols().count(), 6);
+        assert_eq!(
+            graph
+                .attachments()
+                .map(|attachment| attachment.parts.len())
+                .sum::<usize>(),
+            8
+        );
+    }
+
+    #[test]
+    fn test_matching() {
+        let testing_schema: Schema = TEST_SCHEMA.parse().unwrap();
+        let graph = PartGraph::new(&testing_schema);
+
+        let gears = graph.matching("*", 2);
+        let ratios: Vec<u64> = gears.iter().map(|gear| gear.product()).collect();
+        assert_eq!(ratios, vec![16345, 451490]);
+
+        let singles = graph.matching("*", 1);
+        assert_eq!(singles.len(), 1);
+        assert_eq!(singles[0].sum(), 617);
+
+        assert!(graph.matching("#", 2).is_empty());
+        assert_eq!(graph.matching("$", 1)[0].product(), 664);
+    }
+
+    #[test]
+    fn test_components() {
+        let testing_schema: Schema = TEST_SCHEMA.parse().unwrap();
+        let components = PartGraph::new(&testing_schema).components();
+
+        let summary: Vec<(u64, usize, Position, Position)> = components
+            .iter()
+            .map(|component| {
+                (
+                    component.value(),
+                    component.symbols.len(),
+                    component.top_left,
+                    component.bottom_right,
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            summary,
+            vec![
+                (502, 1, Position { x: 0, y: 0 }, Position { x: 3, y: 2 }),
+                (633, 1, Position { x: 6, y: 2 }, Position { x: 8, y: 3 }),
+                (617, 1, Position { x: 0, y: 4 }, Position { x: 3, y: 4 }),
+                (592, 1, Position { x: 2, y: 5 }, Position { x: 5, y: 6 }),
+                (1353, 1, Position { x: 5, y: 7 }, Position { x: 8, y: 9 }),
+                (664, 1, Position { x: 1, y: 8 }, Position { x: 3, y: 9 }),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_components_across_symbols() {
+        let testing_schema: Schema = "1.....\n.*#...\n...2..\n.....@\n...7..".parse().unwrap();
+        let components = PartGraph::new(&testing_schema).components();
+
+        assert_eq!(components.len(), 2);
+        assert_eq!(values(&components[0].parts), vec![1, 2]);
+        assert_eq!(components[0].symbols.len(), 2);
+        assert!(components[1].parts.is_empty());
+        assert_eq!(components[1].value(), 0);
+    }
+
+    #[test]
+    fn test_ranking() {
+        let testing_schema: Schema = TEST_SCHEMA.parse().unwrap();
+        let graph = PartGraph::new(&testing_schema);
+
+        assert_eq!(
+            graph.ranking(),
+            vec![("*", 5), ("#", 1), ("$", 1), ("+", 1)]
+        );
+
+        let testing_schema: Schema = "1*2\n.*.".parse().unwrap();
+        let graph = PartGraph::new(&testing_schema);
+
+        assert_eq!(graph.ranking(), vec![("*", 2)]);
+    }
+}
@@ -1,344 +1,307 @@
-use super::element::Element;
-use super::schema::Schema;
-
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
 pub struct Position {
     pub x: usize,
     pub y: usize,
 }
 
-#[derive(Debug, Clone, Eq, PartialEq)]
-pub enum Edges {
-    LEFT,
-    RIGHT,
-    TOP,
-    TOP_LEFT,
-    TOP_RIGHT,
-    BOTTOM,
-    BOTTOM_LEFT,
-    BOTTOM_RIGHT,
+/// Signed step between two positions, `dx` along a row and `dy` across rows.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct Direction {
+    pub dx: isize,
+    pub dy: isize,
 }
 
-impl Edges {
-    pub fn compute(&self, element: &Element, schema: &Schema) -> Option<Position> {
-        let (el_x, el_y) = (element.position().x, element.position().y);
-        let vec_schema = schema.schema();
-        let x_length = vec_schema.len();
-        let y_length = vec_schema[x_length - 1].len();
-
-        match self {
-            Edges::TOP_LEFT => {
-                let exit_cases: Vec<bool> = vec![
-                    el_x == 0,
-                    (el_x >= 1 && el_x < x_length - 1 && el_y == 0),
-                    (el_x == x_length - 1 && el_y == 0),
-                ];
-
-                for case in exit_cases {
-                    if case {
-                        return None;
-                    }
-                }
-
-                Some(Position {
-                    x: el_x - 1,
-                    y: el_y - 1,
-                })
-            }
-            Edges::TOP => {
-                let exit_cases: Vec<bool> = vec![el_x == 0];
-
-                for case in exit_cases {
-                    if case {
-                        return None;
-                    }
-                }
-
-                Some(Position {
-                    x: el_x - 1,
-                    y: el_y,
-                })
-            }
-            Edges::TOP_RIGHT => {
-                let exit_cases: Vec<bool> = vec![
-                    el_x == 0,
-                    (el_x >= 1 && el_x < x_length - 1 && el_y == 0),
-                    (el_x >= 1 && el_x < x_length - 1 && el_y == y_length - 1),
-                    (el_x == x_length - 1 && el_y == y_length - 1),
-                ];
-
-                for case in exit_cases {
-                    if case {
-                        return None;
-                    }
-                }
+impl Direction {
+    pub const TOP_LEFT: Direction = Direction { dx: -1, dy: -1 };
+    pub const TOP: Direction = Direction { dx: 0, dy: -1 };
+    pub const TOP_RIGHT: Direction = Direction { dx: 1, dy: -1 };
+    pub const LEFT: Direction = Direction { dx: -1, dy: 0 };
+    pub const RIGHT: Direction = Direction { dx: 1, dy: 0 };
+    pub const BOTTOM_LEFT: Direction = Direction { dx: -1, dy: 1 };
+    pub const BOTTOM: Direction = Direction { dx: 0, dy: 1 };
+    pub const BOTTOM_RIGHT: Direction = Direction { dx: 1, dy: 1 };
 
-                Some(Position {
-                    x: el_x - 1,
-                    y: el_y + 1,
-                })
-            }
-            Edges::RIGHT => {
-                let exit_cases: Vec<bool> = vec![
-                    (el_x == 0 && el_y == y_length - 1),
-                    (el_x >= 1 && el_x < x_length - 1 && el_y == y_length - 1),
-                    (el_x == x_length - 1 && el_y == y_length - 1),
-                ];
-
-                for case in exit_cases {
-                    if case {
-                        return None;
-                    }
-                }
+    /// The 4-connected neighbourhood, in reading order.
+    pub const CARDINAL: [Direction; 4] = [
+        Direction::TOP,
+        Direction::LEFT,
+        Direction::RIGHT,
+        Direction::BOTTOM,
+    ];
 
-                Some(Position {
-                    x: el_x,
-                    y: el_y + 1,
-                })
-            }
-            Edges::BOTTOM_RIGHT => {
-                let exit_cases: Vec<bool> = vec![
-                    el_x == x_length - 1,
-                    (el_x == 0 && el_y == y_length - 1),
-                    (el_x >= 1 && el_x < x_length - 1 && el_y == y_length - 1),
-                ];
+    /// The 8-connected neighbourhood, in reading order.
+    pub const ALL: [Direction; 8] = [
+        Direction::TOP_LEFT,
+        Direction::TOP,
+        Direction::TOP_RIGHT,
+        Direction::LEFT,
+        Direction::RIGHT,
+        Direction::BOTTOM_LEFT,
+        Direction::BOTTOM,
+        Direction::BOTTOM_RIGHT,
+    ];
 
-                for case in exit_cases {
-                    if case {
-                        return None;
-                    }
-                }
+    pub fn new(dx: isize, dy: isize) -> Direction {
+        Direction { dx, dy }
+    }
 
-                Some(Position {
-                    x: el_x + 1,
-                    y: el_y + 1,
-                })
-            }
-            Edges::BOTTOM => {
-                let exit_cases: Vec<bool> = vec![el_x == x_length - 1];
+    /// Every step of at most `radius` cells in both axes, origin excluded, in reading order.
+    pub fn within(radius: usize) -> Vec<Direction> {
+        let radius = radius as isize;
+        let mut directions: Vec<Direction> = Vec::new();
 
-                for case in exit_cases {
-                    if case {
-                        return None;
-                    }
+        for dy in -radius..=radius {
+            for dx in -radius..=radius {
+                if dx != 0 || dy != 0 {
+                    directions.push(Direction { dx, dy });
                 }
-
-                Some(Position {
-                    x: el_x + 1,
-                    y: el_y,
-                })
             }
-            Edges::BOTTOM_LEFT => {
-                let exit_cases: Vec<bool> = vec![
-                    el_x == x_length - 1,
-                    (el_x == 0 && el_y == 0),
-                    (el_x >= 1 && el_x < x_length - 1 && el_y == 0),
-                ];
+        }
 
-                for case in exit_cases {
-                    if case {
-                        return None;
-                    }
-                }
+        directions
+    }
+}
 
-                Some(Position {
-                    x: el_x + 1,
-                    y: el_y - 1,
-                })
-            }
-            Edges::LEFT => {
-                let exit_cases: Vec<bool> = vec![
-                    (el_x == 0 && el_y == 0),
-                    (el_x >= 1 && el_x < x_length - 1 && el_y == 0),
-                    (el_x == x_length - 1 && el_y == 0),
-                ];
+impl Position {
+    pub fn offset(&self, direction: Direction) -> Option<Position> {
+        Some(Position {
+            x: self.x.checked_add_signed(direction.dx)?,
+            y: self.y.checked_add_signed(direction.dy)?,
+        })
+    }
 
-                for case in exit_cases {
-                    if case {
-                        return None;
-                    }
-                }
+    /// Like `offset`, but also rejects positions outside a `width` x `height` area.
+    pub fn offset_within(
+        &self,
+        direction: Direction,
+        width: usize,
+        height: usize,
+    ) -> Option<Position> {
+        self.offset(direction)
+            .filter(|position| position.x < width && position.y < height)
+    }
 
-                Some(Position {
-                    x: el_x,
-                    y: el_y - 1,
-                })
-            }
-        }
+    pub fn neighbourhood(&self, radius: usize) -> impl Iterator<Item = Position> + '_ {
+        Direction::within(radius)
+            .into_iter()
+            .filter_map(|direction| self.offset(direction))
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::super::Schema;
     use super::*;
-    use std::fs;
 
-    #[test]
-    fn test_single_edges() {
-        let testing_schema = Schema::from_file("src/schema/test_schema_input.txt");
-        let top_left_element = testing_schema.get(Position { x: 0, y: 0 }).unwrap();
+    type DirectionCase = ((usize, usize), Option<Position>);
 
-        let tl_position = Edges::TOP_LEFT;
-        let left_position = Edges::LEFT;
-        let right_position = Edges::RIGHT;
+    #[test]
+    fn test_single_directions() {
+        let top_left = Position { x: 0, y: 0 };
 
+        assert_eq!(top_left.offset(Direction::TOP_LEFT), None);
+        assert_eq!(top_left.offset(Direction::LEFT), None);
         assert_eq!(
-            tl_position.compute(&top_left_element, &testing_schema),
-            None
+            top_left.offset(Direction::RIGHT),
+            Some(Position { x: 1, y: 0 })
         );
         assert_eq!(
-            left_position.compute(&top_left_element, &testing_schema),
-            None
+            top_left.offset(Direction::new(3, 7)),
+            Some(Position { x: 3, y: 7 })
         );
         assert_eq!(
-            right_position.compute(&top_left_element, &testing_schema),
-            Some(Position { x: 0, y: 1 })
+            Position {
+                x: usize::MAX,
+                y: 0
+            }
+            .offset(Direction::RIGHT),
+            None
         );
     }
 
     #[test]
-    fn test_edges() {
-        let testing_schema = Schema::from_file("src/schema/test_schema_input.txt");
+    fn test_directions() {
+        let testing_schema = Schema::from_file("src/schema/test_schema_input.txt").unwrap();
 
-        let cases: Vec<(Edges, Vec<((usize, usize), Option<Position>)>)> = vec![
+        let cases: Vec<(Direction, Vec<DirectionCase>)> = vec![
             (
-                Edges::TOP_LEFT,
+                Direction::TOP_LEFT,
                 vec![
                     ((0, 0), None),
-                    ((0, 1), None),
-                    ((0, 2), None),
                     ((1, 0), None),
-                    ((1, 1), Some(Position { x: 0, y: 0 })),
-                    ((1, 2), Some(Position { x: 0, y: 1 })),
                     ((2, 0), None),
+                    ((0, 1), None),
+                    ((1, 1), Some(Position { x: 0, y: 0 })),
                     ((2, 1), Some(Position { x: 1, y: 0 })),
+                    ((0, 2), None),
+                    ((1, 2), Some(Position { x: 0, y: 1 })),
                     ((2, 2), Some(Position { x: 1, y: 1 })),
                 ],
             ),
             (
-                Edges::TOP,
+                Direction::TOP,
                 vec![
                     ((0, 0), None),
-                    ((0, 1), None),
-                    ((0, 2), None),
-                    ((1, 0), Some(Position { x: 0, y: 0 })),
-                    ((1, 1), Some(Position { x: 0, y: 1 })),
-                    ((1, 2), Some(Position { x: 0, y: 2 })),
-                    ((2, 0), Some(Position { x: 1, y: 0 })),
-                    ((2, 1), Some(Position { x: 1, y: 1 })),
-                    ((2, 2), Some(Position { x: 1, y: 2 })),
+                    ((1, 0), None),
+                    ((2, 0), None),
+                    ((0, 1), Some(Position { x: 0, y: 0 })),
+                    ((1, 1), Some(Position { x: 1, y: 0 })),
+                    ((2, 1), Some(Position { x: 2, y: 0 })),
+                    ((0, 2), Some(Position { x: 0, y: 1 })),
+                    ((1, 2), Some(Position { x: 1, y: 1 })),
+                    ((2, 2), Some(Position { x: 2, y: 1 })),
                 ],
             ),
             (
-                Edges::TOP_RIGHT,
+                Direction::TOP_RIGHT,
                 vec![
                     ((0, 0), None),
-                    ((0, 1), None),
-                    ((0, 2), None),
                     ((1, 0), None),
-                    ((1, 1), Some(Position { x: 0, y: 2 })),
-                    ((1, 2), None),
-                    ((2, 0), Some(Position { x: 1, y: 1 })),
-                    ((2, 1), Some(Position { x: 1, y: 2 })),
+                    ((2, 0), None),
+                    ((0, 1), Some(Position { x: 1, y: 0 })),
+                    ((1, 1), Some(Position { x: 2, y: 0 })),
+                    ((2, 1), None),
+                    ((0, 2), Some(Position { x: 1, y: 1 })),
+                    ((1, 2), Some(Position { x: 2, y: 1 })),
                     ((2, 2), None),
                 ],
             ),
             (
-                Edges::RIGHT,
+                Direction::RIGHT,
                 vec![
-                    ((0, 0), Some(Position { x: 0, y: 1 })),
-                    ((0, 1), Some(Position { x: 0, y: 2 })),
-                    ((0, 2), None),
-                    ((1, 0), Some(Position { x: 1, y: 1 })),
-                    ((1, 1), Some(Position { x: 1, y: 2 })),
-                    ((1, 2), None),
-                    ((2, 0), Some(Position { x: 2, y: 1 })),
-                    ((2, 1), Some(Position { x: 2, y: 2 })),
+                    ((0, 0), Some(Position { x: 1, y: 0 })),
+                    ((1, 0), Some(Position { x: 2, y: 0 })),
+                    ((2, 0), None),
+                    ((0, 1), Some(Position { x: 1, y: 1 })),
+                    ((1, 1), Some(Position { x: 2, y: 1 })),
+                    ((2, 1), None),
+                    ((0, 2), Some(Position { x: 1, y: 2 })),
+                    ((1, 2), Some(Position { x: 2, y: 2 })),
                     ((2, 2), None),
                 ],
             ),
             (
-                Edges::BOTTOM_RIGHT,
+                Direction::BOTTOM_RIGHT,
                 vec![
                     ((0, 0), Some(Position { x: 1, y: 1 })),
-                    ((0, 1), Some(Position { x: 1, y: 2 })),
-                    ((0, 2), None),
                     ((1, 0), Some(Position { x: 2, y: 1 })),
-                    ((1, 1), Some(Position { x: 2, y: 2 })),
-                    ((1, 2), None),
                     ((2, 0), None),
+                    ((0, 1), Some(Position { x: 1, y: 2 })),
+                    ((1, 1), Some(Position { x: 2, y: 2 })),
                     ((2, 1), None),
+                    ((0, 2), None),
+                    ((1, 2), None),
                     ((2, 2), None),
                 ],
             ),
             (
-                Edges::BOTTOM,
+                Direction::BOTTOM,
                 vec![
-                    ((0, 0), Some(Position { x: 1, y: 0 })),
-                    ((0, 1), Some(Position { x: 1, y: 1 })),
-                    ((0, 2), Some(Position { x: 1, y: 2 })),
-                    ((1, 0), Some(Position { x: 2, y: 0 })),
-                    ((1, 1), Some(Position { x: 2, y: 1 })),
-                    ((1, 2), Some(Position { x: 2, y: 2 })),
-                    ((2, 0), None),
-                    ((2, 1), None),
+                    ((0, 0), Some(Position { x: 0, y: 1 })),
+                    ((1, 0), Some(Position { x: 1, y: 1 })),
+                    ((2, 0), Some(Position { x: 2, y: 1 })),
+                    ((0, 1), Some(Position { x: 0, y: 2 })),
+                    ((1, 1), Some(Position { x: 1, y: 2 })),
+                    ((2, 1), Some(Position { x: 2, y: 2 })),
+                    ((0, 2), None),
+                    ((1, 2), None),
                     ((2, 2), None),
                 ],
             ),
             (
-                Edges::BOTTOM_LEFT,
+                Direction::BOTTOM_LEFT,
                 vec![
                     ((0, 0), None),
-                    ((0, 1), Some(Position { x: 1, y: 0 })),
-                    ((0, 2), Some(Position { x: 1, y: 1 })),
-                    ((1, 0), None),
-                    ((1, 1), Some(Position { x: 2, y: 0 })),
-                    ((1, 2), Some(Position { x: 2, y: 1 })),
-                    ((2, 0), None),
-                    ((2, 1), None),
+                    ((1, 0), Some(Position { x: 0, y: 1 })),
+                    ((2, 0), Some(Position { x: 1, y: 1 })),
+                    ((0, 1), None),
+                    ((1, 1), Some(Position { x: 0, y: 2 })),
+                    ((2, 1), Some(Position { x: 1, y: 2 })),
+                    ((0, 2), None),
+                    ((1, 2), None),
                     ((2, 2), None),
                 ],
             ),
             (
-                Edges::LEFT,
+                Direction::LEFT,
                 vec![
                     ((0, 0), None),
-                    ((0, 1), Some(Position { x: 0, y: 0 })),
-                    ((0, 2), Some(Position { x: 0, y: 1 })),
-                    ((1, 0), None),
-                    ((1, 1), Some(Position { x: 1, y: 0 })),
-                    ((1, 2), Some(Position { x: 1, y: 1 })),
-                    ((2, 0), None),
-                    ((2, 1), Some(Position { x: 2, y: 0 })),
-                    ((2, 2), Some(Position { x: 2, y: 1 })),
+                    ((1, 0), Some(Position { x: 0, y: 0 })),
+                    ((2, 0), Some(Position { x: 1, y: 0 })),
+                    ((0, 1), None),
+                    ((1, 1), Some(Position { x: 0, y: 1 })),
+                    ((2, 1), Some(Position { x: 1, y: 1 })),
+                    ((0, 2), None),
+                    ((1, 2), Some(Position { x: 0, y: 2 })),
+                    ((2, 2), Some(Position { x: 1, y: 2 })),
                 ],
             ),
         ];
 
-        println!("-----");
-        for (case_index, (edge, data)) in cases.iter().enumerate() {
-            //
-            for (result_index, (target_coordinates, result_element_position)) in
-                data.iter().enumerate()
-            {
-                //
-                println!("case_index: {}, result_index: {}", case_index, result_index);
-                println!("target_coordinates: {:?}", target_coordinates);
-                println!("edge: {:?}", edge);
-                let extracted_element = testing_schema
-                    .get(Position {
-                        x: target_coordinates.0,
-                        y: target_coordinates.1,
-                    })
-                    .unwrap();
-                println!("extracted_element: {:?}", extracted_element);
+        for (direction, data) in cases.iter() {
+            for (target_coordinates, result_position) in data.iter() {
+                let target = Position {
+                    x: target_coordinates.0,
+                    y: target_coordinates.1,
+                };
+                assert!(testing_schema.contains(target));
 
-                let computed_position = edge.compute(&extracted_element, &testing_schema);
-                println!("computed_position: {:?}", computed_position);
-                //
-                println!("-----");
-                assert_eq!(&computed_position, result_element_position);
+                let computed_position = target
+                    .offset(*direction)
+                    .filter(|position| testing_schema.contains(*position));
+                assert_eq!(
+                    &computed_position, result_position,
+                    "{:?} from {:?}",
+                    direction, target
+                );
+
+                let bounded_position = target.offset_within(
+                    *direction,
+                    testing_schema.width(),
+                    testing_schema.height(),
+                );
+                assert_eq!(
+                    &bounded_position, result_position,
+                    "{:?} from {:?}",
+                    direction, target
+                );
             }
         }
     }
+
+    #[test]
+    fn test_non_square_offsets() {
+        let position = Position { x: 9, y: 1 };
+
+        assert_eq!(position.offset_within(Direction::RIGHT, 10, 2), None);
+        assert_eq!(position.offset_within(Direction::BOTTOM, 10, 2), None);
+        assert_eq!(
+            position.offset_within(Direction::TOP_LEFT, 10, 2),
+            Some(Position { x: 8, y: 0 })
+        );
+        assert_eq!(
+            position.offset_within(Direction::new(-9, 0), 10, 2),
+            Some(Position { x: 0, y: 1 })
+        );
+    }
+
+    #[test]
+    fn test_neighbourhoods() {
+        assert_eq!(Direction::within(0), vec![]);
+        assert_eq!(Direction::within(1), Direction::ALL.to_vec());
+        assert_eq!(Direction::within(2).len(), 24);
+
+        let corner = Position { x: 0, y: 0 };
+        let neighbourhood: Vec<Position> = corner.neighbourhood(1).collect();
+        assert_eq!(
+            neighbourhood,
+            vec![
+                Position { x: 1, y: 0 },
+                Position { x: 0, y: 1 },
+                Position { x: 1, y: 1 }
+            ]
+        );
+        assert_eq!(corner.neighbourhood(2).count(), 8);
+        assert_eq!(Position { x: 5, y: 5 }.neighbourhood(3).count(), 48);
+    }
 }
@@ -0,0 +1,234 @@
+use std::collections::HashSet;
+
+use super::element::Element;
+use super::position::Position;
+use super::schema::Schema;
+
+const ANSI_RESET: &str = "\x1b[0m";
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum RenderStyle {
+    Plain,
+    Brackets,
+    Ansi,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Highlight {
+    None,
+    ValidPart,
+    InvalidNumber,
+    Symbol,
+    Gear,
+}
+
+impl Highlight {
+    fn markers(&self, style: RenderStyle) -> (&'static str, &'static str) {
+        match (style, self) {
+            (RenderStyle::Plain, _) | (_, Highlight::None) => ("", ""),
+            (RenderStyle::Brackets, Highlight::ValidPart) => ("[", "]"),
+            (RenderStyle::Brackets, Highlight::InvalidNumber) => ("(", ")"),
+            (RenderStyle::Brackets, Highlight::Symbol) => ("{", "}"),
+            (RenderStyle::Brackets, Highlight::Gear) => ("<", ">"),
+            (RenderStyle::Ansi, Highlight::ValidPart) => ("\x1b[32m", ANSI_RESET),
+            (RenderStyle::Ansi, Highlight::InvalidNumber) => ("\x1b[31m", ANSI_RESET),
+            (RenderStyle::Ansi, Highlight::Symbol) => ("\x1b[33m", ANSI_RESET),
+            (RenderStyle::Ansi, Highlight::Gear) => ("\x1b[1;35m", ANSI_RESET),
+        }
+    }
+}
+
+/// Reprints a parsed schema, marking valid parts, invalid numbers, symbols and gears.
+///
+/// Bracket markers are inserted around elements, so columns do not line up
+/// with the original input; the ANSI style keeps the layout intact.
+#[derive(Debug, Clone)]
+pub struct Renderer<'a> {
+    schema: &'a Schema,
+    style: RenderStyle,
+    region: Option<(Position, usize)>,
+    part_positions: HashSet<Position>,
+    gear_positions: HashSet<Position>,
+}
+
+impl<'a> Renderer<'a> {
+    pub fn new(schema: &'a Schema) -> Renderer<'a> {
+        Renderer {
+            schema,
+            style: RenderStyle::Brackets,
+            region: None,
+            part_positions: schema.parts().iter().map(|part| *part.position()).collect(),
+            gear_positions: schema
+                .gears()
+                .iter()
+                .map(|gear| *gear.symbol.position())
+                .collect(),
+        }
+    }
+
+    pub fn style(mut self, style: RenderStyle) -> Renderer<'a> {
+        self.style = style;
+        self
+    }
+
+    /// Only renders the cells at most `radius` rows and columns away from `position`.
+    pub fn around(mut self, position: Position, radius: usize) -> Renderer<'a> {
+        self.region = Some((position, radius));
+        self
+    }
+
+    pub fn highlight(&self, element: &Element) -> Highlight {
+        let position = element.position();
+
+        match element {
+            Element::Number(..) if self.part_positions.contains(position) => Highlight::ValidPart,
+            Element::Number(..) => Highlight::InvalidNumber,
+            Element::Symbol(_) if self.gear_positions.contains(position) => Highlight::Gear,
+            Element::Symbol(_) => Highlight::Symbol,
+            Element::Dot(_) | Element::Unknown(_) => Highlight::None,
+        }
+    }
+
+    fn bounds(&self) -> (Position, Position) {
+        let last = Position {
+            x: self.schema.width().saturating_sub(1),
+            y: self.schema.height().saturating_sub(1),
+        };
+
+        match self.region {
+            Some((center, radius)) => (
+                Position {
+                    x: center.x.saturating_sub(radius),
+                    y: center.y.saturating_sub(radius),
+                },
+                Position {
+                    x: last.x.min(center.x.saturating_add(radius)),
+                    y: last.y.min(center.y.saturating_add(radius)),
+                },
+            ),
+            None => (Position { x: 0, y: 0 }, last),
+        }
+    }
+
+    pub fn render(&self) -> String {
+        let mut output = String::new();
+
+        if self.schema.height() == 0 {
+            return output;
+        }
+
+        let (top_left, bottom_right) = self.bounds();
+
+        for y in top_left.y..=bottom_right.y {
+            for x in top_left.x..=bottom_right.x {
+                let element = match self.schema.get(Position { x, y }) {
+                    Some(element) => element,
+                    None => break,
+                };
+                let start = element.position().x;
+                let end = start + element.width() - 1;
+
+                let (open, close) = self.highlight(element).markers(self.style);
+
+                if x == start.max(top_left.x) {
+                    output.push_str(open);
+                }
+
+                output.extend(element.value().chars().nth(x - start));
+
+                if x == end.min(bottom_right.x) {
+                    output.push_str(close);
+                }
+            }
+
+            if y < bottom_right.y {
+                output.push('\n');
+            }
+        }
+
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_SCHEMA: &str = "467..114..\n...*......\n..35..633.\n......#...";
+
+    #[test]
+    fn test_render_brackets() {
+        let testing_schema: Schema = TEST_SCHEMA.parse().unwrap();
+        let rendered = Renderer::new(&testing_schema).render();
+
+        assert_eq!(
+            rendered,
+            "[467]..(114)..\n...<*>......\n..[35]..[633].\n......{#}..."
+        );
+
+        let testing_schema: Schema = "467\n..*\n..3".parse().unwrap();
+        let rendered = Renderer::new(&testing_schema).render();
+
+        assert_eq!(rendered, "[467]\n..<*>\n..[3]");
+    }
+
+    #[test]
+    fn test_render_plain() {
+        let testing_schema: Schema = TEST_SCHEMA.parse().unwrap();
+        let rendered = Renderer::new(&testing_schema)
+            .style(RenderStyle::Plain)
+            .render();
+
+        assert_eq!(rendered, TEST_SCHEMA);
+    }
+
+    #[test]
+    fn test_render_ansi() {
+        let testing_schema: Schema = "12.\n.#.".parse().unwrap();
+        let rendered = Renderer::new(&testing_schema)
+            .style(RenderStyle::Ansi)
+            .render();
+
+        assert_eq!(rendered, "\x1b[32m12\x1b[0m.\n.\x1b[33m#\x1b[0m.");
+    }
+
+    #[test]
+    fn test_render_around() {
+        let testing_schema: Schema = TEST_SCHEMA.parse().unwrap();
+
+        let rendered = Renderer::new(&testing_schema)
+            .around(Position { x: 3, y: 1 }, 1)
+            .render();
+        assert_eq!(rendered, "[7]..\n.<*>.\n[35].");
+
+        let rendered = Renderer::new(&testing_schema)
+            .around(Position { x: 0, y: 0 }, 1)
+            .render();
+        assert_eq!(rendered, "[46]\n..");
+    }
+
+    #[test]
+    fn test_highlight() {
+        let testing_schema: Schema = TEST_SCHEMA.parse().unwrap();
+        let renderer = Renderer::new(&testing_schema);
+
+        let highlights: Vec<Highlight> = testing_schema
+            .elements()
+            .iter()
+            .filter(|element| !matches!(element, Element::Dot(_)))
+            .map(|element| renderer.highlight(element))
+            .collect();
+
+        assert_eq!(
+            highlights,
+            vec![
+                Highlight::ValidPart,
+                Highlight::InvalidNumber,
+                Highlight::Gear,
+                Highlight::ValidPart,
+                Highlight::ValidPart,
+                Highlight::Symbol,
+            ]
+        );
+    }
+}
@@ -1,219 +1,583 @@
-use std::{fs, usize};
+use std::{fs, io::Read, str::FromStr};
 
-use crate::Scanner;
+use common::{Json, Level, Observer, Silent, Snapshot, Solution, Table};
 
-use super::element::{Element, ElementProps};
+use super::classifier::{CellClassifier, DefaultClassifier};
+use super::component::Component;
+use super::element::Element;
 use super::engine_part::EnginePart;
-use super::position::{Edges, Position};
+use super::error::SchemaError;
+use super::gear::Gear;
+use super::grid::{Connectivity, Grid};
+use super::part_graph::PartGraph;
+use super::position::Position;
+use super::render::{RenderStyle, Renderer};
+use super::symbol_index::SymbolIndex;
+use super::tokenizer::tokenize_row;
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Schema {
-    schema: Vec<Vec<Element>>,
+    elements: Vec<Element>,
+    index: Grid<usize>,
+    symbol_index: SymbolIndex,
     parts: Vec<EnginePart>,
+    gears: Vec<Gear>,
+}
+
+impl Default for Schema {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FromStr for Schema {
+    type Err = SchemaError;
+
+    fn from_str(content: &str) -> Result<Self, Self::Err> {
+        Schema::from_str_with(content, &DefaultClassifier)
+    }
 }
 
 impl Schema {
     pub fn new() -> Schema {
         Schema {
-            schema: Vec::new(),
+            elements: Vec::new(),
+            index: Grid::new(),
+            symbol_index: SymbolIndex::default(),
             parts: Vec::new(),
+            gears: Vec::new(),
         }
     }
 
-    pub fn from_file(file_path: &str) -> Schema {
-        match fs::read_to_string(file_path) {
-            Ok(content) => {
-                //
-                let mut schema_vec: Vec<Vec<Element>> = Vec::new();
-                let lines: Vec<&str> = content.lines().collect();
-                println!("total lines on file: {}", lines.len());
-
-                let mut all_parts: Vec<EnginePart> = Vec::new();
-                let mut new_part: Vec<Element> = Vec::new();
-
-                let mut line_number: usize = 0;
-
-                for line in lines {
-                    //println!("line nuber: {:?}", line_number);
-                    //println!("line: {:?}", line);
-
-                    let mut line_vec: Vec<Element> = Vec::new();
-                    let mut line_scanner = Scanner::new(line);
-
-                    //let mut new_part: Vec<Element> = Vec::new();
-
-                    let mut x_line: usize = 0;
-
-                    line_scanner.scan(|character| {
-                        let y_line = line_number.clone();
-
-                        if character.eq(&'.') {
-                            if !new_part.is_empty() {
-                                all_parts.push(EnginePart {
-                                    elements: new_part.clone(),
-                                });
-                                new_part = Vec::new();
-                            }
-
-                            line_vec.push(Element::Dot(ElementProps {
-                                position: Position {
-                                    x: x_line,
-                                    y: y_line,
-                                },
-                                value: character.to_string(),
-                                width: 1,
-                            }))
-                        };
-
-                        if character.is_alphabetic() {
-                            line_vec.push(Element::Unknown(ElementProps {
-                                position: Position {
-                                    x: x_line,
-                                    y: y_line,
-                                },
-                                value: character.to_string(),
-                                width: 1,
-                            }))
-                        };
-
-                        if character.is_numeric() {
-                            let new_element = Element::Number(ElementProps {
-                                position: Position {
-                                    x: x_line,
-                                    y: y_line,
-                                },
-                                value: character.to_string(),
-                                width: 1,
-                            });
-
-                            new_part.push(new_element.clone());
-
-                            line_vec.push(new_element)
-                        }
-
-                        if !character.eq(&'.')
-                            && !character.is_numeric()
-                            && !character.is_alphabetic()
-                        {
-                            line_vec.push(Element::Symbol(ElementProps {
-                                position: Position {
-                                    x: x_line,
-                                    y: y_line,
-                                },
-                                value: character.to_string(),
-                                width: 1,
-                            }))
-                        }
-
-                        x_line += 1;
-                        Some(character.clone())
-                    });
-
-                    //println!("line_vec: {:?}", line_vec);
-                    //println!("-----------\nall_parts: {:#?}", all_parts);
-                    schema_vec.push(line_vec);
-                    line_number += 1;
-                }
+    pub fn from_file(file_path: &str) -> Result<Schema, SchemaError> {
+        Schema::from_file_with(file_path, &DefaultClassifier)
+    }
+
+    pub fn from_file_with(
+        file_path: &str,
+        classifier: &dyn CellClassifier,
+    ) -> Result<Schema, SchemaError> {
+        let content = fs::read_to_string(file_path)?;
+        Schema::from_str_with(&content, classifier)
+    }
 
-                let mut result = Schema {
-                    schema: schema_vec,
-                    parts: Vec::new(),
-                };
-                println!("total number of parts found: {}", all_parts.len());
-                result.parse_parts(all_parts);
+    pub fn from_reader(reader: impl Read) -> Result<Schema, SchemaError> {
+        Schema::from_reader_with(reader, &DefaultClassifier)
+    }
+
+    pub fn from_reader_with(
+        mut reader: impl Read,
+        classifier: &dyn CellClassifier,
+    ) -> Result<Schema, SchemaError> {
+        let mut content = String::new();
+        reader.read_to_string(&mut content)?;
+        Schema::from_str_with(&content, classifier)
+    }
 
-                result
+    pub fn from_str_with(
+        content: &str,
+        classifier: &dyn CellClassifier,
+    ) -> Result<Schema, SchemaError> {
+        Schema::from_str_observed(content, classifier, &mut Silent)
+    }
+
+    /// `from_str_with`, reporting the lines, every number and the parts and
+    /// gears found to `observer`.
+    pub fn from_str_observed(
+        content: &str,
+        classifier: &dyn CellClassifier,
+        observer: &mut dyn Observer,
+    ) -> Result<Schema, SchemaError> {
+        let mut result = Schema::new();
+
+        for (line_number, line) in content.lines().enumerate() {
+            let mut line_vec: Vec<usize> = Vec::new();
+
+            for element in tokenize_row(line_number, line, classifier)? {
+                result.push_element(element, &mut line_vec);
             }
-            Err(err) => {
-                eprintln!("Error in reading file '{}': {}", file_path, err);
-                Schema {
-                    schema: Vec::new(),
-                    parts: Vec::new(),
-                }
+
+            result.index.push_row(line_vec);
+        }
+
+        result.symbol_index = SymbolIndex::build(&result.elements, &result.index);
+        result.parse_parts();
+        result.parse_gears();
+
+        observer.event(
+            Level::Info,
+            format_args!("total lines on input: {}", result.height()),
+        );
+        for (element_index, element) in result.elements.iter().enumerate() {
+            if let Some(value) = element.number() {
+                let position = element.position();
+                observer.event(
+                    Level::Debug,
+                    format_args!(
+                        "number {} at ({}, {}) is part: {}",
+                        value,
+                        position.x,
+                        position.y,
+                        result.symbol_index.is_part(element_index)
+                    ),
+                );
             }
         }
+        observer.event(
+            Level::Info,
+            format_args!("total number of parts found: {}", result.parts.len()),
+        );
+        observer.event(
+            Level::Info,
+            format_args!("total number of gears found: {}", result.gears.len()),
+        );
+
+        Ok(result)
+    }
+
+    fn push_element(&mut self, element: Element, line_vec: &mut Vec<usize>) {
+        let element_index = self.elements.len();
+
+        line_vec.extend(std::iter::repeat_n(element_index, element.width()));
+        self.elements.push(element);
     }
 
-    pub fn schema(&self) -> &Vec<Vec<Element>> {
-        &self.schema
+    pub fn elements(&self) -> &Vec<Element> {
+        &self.elements
+    }
+
+    /// Maps every cell of the schema to the index of the element covering it.
+    pub fn index(&self) -> &Grid<usize> {
+        &self.index
+    }
+
+    pub fn symbol_index(&self) -> &SymbolIndex {
+        &self.symbol_index
+    }
+
+    pub fn part_graph(&self) -> PartGraph<'_> {
+        PartGraph::new(self)
+    }
+
+    /// The assemblies of the schema: parts and symbols linked by touching,
+    /// see `PartGraph::components`.
+    pub fn components(&self) -> Vec<Component<'_>> {
+        self.part_graph().components()
+    }
+
+    pub fn width(&self) -> usize {
+        self.index.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.index.height()
+    }
+
+    pub fn contains(&self, position: Position) -> bool {
+        self.index.contains(position)
     }
 
     pub fn parts(&self) -> &Vec<EnginePart> {
         &self.parts
     }
 
-    pub fn get(&self, position: Position) -> Option<&Element> {
-        if self.schema.len() == 0 {
-            return None;
-        }
+    pub fn gears(&self) -> &Vec<Gear> {
+        &self.gears
+    }
 
-        if position.y >= self.schema.len() {
-            return None;
-        }
+    pub fn numbers(&self) -> impl Iterator<Item = &Element> {
+        self.elements.iter().filter(|element| element.is_number())
+    }
 
-        if position.x >= self.schema[0].len() {
-            return None;
-        }
+    pub fn element_index(&self, position: Position) -> Option<usize> {
+        self.index.get(position).copied()
+    }
 
-        Some(&self.schema[position.y][position.x])
+    pub fn get(&self, position: Position) -> Option<&Element> {
+        self.element_index(position)
+            .map(|element_index| &self.elements[element_index])
     }
 
-    pub fn parse_parts(&mut self, parts_list: Vec<EnginePart>) {
-        let mut validated_parts: Vec<EnginePart> = vec![];
+    /// Distinct elements touching any cell of `element`, diagonals included.
+    pub fn adjacent(&self, element: &Element) -> Vec<&Element> {
+        let own_index = self.element_index(*element.position());
+        let mut adjacent_indexes: Vec<usize> = element
+            .cells()
+            .flat_map(|cell| self.index.neighbours(cell, Connectivity::Eight))
+            .map(|(_, element_index)| *element_index)
+            .filter(|element_index| Some(*element_index) != own_index)
+            .collect();
+
+        adjacent_indexes.sort_unstable();
+        adjacent_indexes.dedup();
+
+        adjacent_indexes
+            .into_iter()
+            .map(|element_index| &self.elements[element_index])
+            .collect()
+    }
 
-        for part in &parts_list {
-            let mut is_valid = false;
-            for element in &part.elements {
-                if self.collides_with_symbol(element.clone()) {
-                    //self.parts.push(part.clone());
-                    is_valid = true;
-                }
-            }
-            println!("is part valid: {}", is_valid);
+    pub fn parse_parts(&mut self) {
+        let mut validated_parts: Vec<EnginePart> = vec![];
 
-            if is_valid {
+        for (element_index, element) in self.elements.iter().enumerate() {
+            if self.symbol_index.is_part(element_index) {
                 //
-                validated_parts.push(part.clone());
+                validated_parts.push(EnginePart {
+                    element: element.clone(),
+                });
             }
         }
         self.parts = validated_parts;
     }
 
+    pub fn parse_gears(&mut self) {
+        let gears: Vec<Gear> = self
+            .part_graph()
+            .matching("*", 2)
+            .into_iter()
+            .map(|attachment| Gear {
+                symbol: attachment.symbol.clone(),
+                parts: (
+                    EnginePart {
+                        element: attachment.parts[0].clone(),
+                    },
+                    EnginePart {
+                        element: attachment.parts[1].clone(),
+                    },
+                ),
+            })
+            .collect();
+
+        self.gears = gears;
+    }
+
     pub fn has_symbol(&self, position: Position) -> bool {
         //
-        match self.get(position) {
-            Some(Element::Symbol(_)) => true,
-            _ => false,
+        matches!(self.get(position), Some(Element::Symbol(_)))
+    }
+
+    pub fn touches_symbol(&self, position: Position) -> bool {
+        self.symbol_index.touches_symbol(position)
+    }
+
+    pub fn collides_with_symbol(&self, element: &Element) -> bool {
+        self.element_index(*element.position())
+            .is_some_and(|element_index| self.symbol_index.is_part(element_index))
+    }
+
+    /// Symbols adjacent to a number of the schema, in schema order.
+    pub fn symbols_touching(&self, element: &Element) -> Vec<&Element> {
+        match self.element_index(*element.position()) {
+            Some(element_index) => self
+                .symbol_index
+                .symbols_touching(element_index)
+                .iter()
+                .map(|symbol| &self.elements[*symbol])
+                .collect(),
+            None => Vec::new(),
         }
     }
+}
 
-    pub fn collides_with_symbol(&self, element: Element) -> bool {
-        let edges = vec![
-            Edges::TOP_LEFT,
-            Edges::TOP,
-            Edges::TOP_RIGHT,
-            Edges::LEFT,
-            Edges::RIGHT,
-            Edges::BOTTOM_LEFT,
-            Edges::BOTTOM,
-            Edges::BOTTOM_RIGHT,
-        ];
-
-        for edge in edges {
-            let computed_element = edge.compute(&element, self);
-            match computed_element {
-                Some(position) => {
-                    let element = self.get(position).unwrap();
-
-                    match element {
-                        Element::Symbol(_) => return true,
-                        _ => continue,
-                    }
+/// The size of the grid, its bracket rendering, then every number and symbol
+/// with what the schema made of it.
+impl Snapshot for Schema {
+    fn snapshot(&self) -> String {
+        let mut snapshot = format!(
+            "{}x{}, {} elements\n\n",
+            self.width(),
+            self.height(),
+            self.elements.len()
+        );
+        snapshot.push_str(&Renderer::new(self).style(RenderStyle::Brackets).render());
+        snapshot.push('\n');
+
+        for element in &self.elements {
+            let Position { x, y } = *element.position();
+            let kind = match element {
+                Element::Number(..) if self.collides_with_symbol(element) => "part",
+                Element::Number(..) => "number",
+                Element::Symbol(_) if self.gears.iter().any(|gear| gear.symbol == *element) => {
+                    "gear"
                 }
-                None => continue,
+                Element::Symbol(_) => "symbol",
+                Element::Unknown(_) => "unknown",
+                Element::Dot(_) => continue,
+            };
+
+            snapshot.push_str(&format!("({}, {}) {} {}\n", x, y, kind, element.value()));
+        }
+
+        snapshot
+    }
+}
+
+impl Solution for Schema {
+    type PartOne = u64;
+    type PartTwo = u64;
+    type Error = SchemaError;
+
+    fn parse(input: &str) -> Result<Self, Self::Error> {
+        input.parse()
+    }
+
+    fn parse_observed(input: &str, observer: &mut dyn Observer) -> Result<Self, Self::Error> {
+        Schema::from_str_observed(input, &DefaultClassifier, observer)
+    }
+
+    /// Sum of the numbers touching a symbol.
+    fn part_one(&self) -> u64 {
+        self.parts.iter().map(|part| part.value() as u64).sum()
+    }
+
+    /// Sum of the ratios of the gears.
+    fn part_two(&self) -> u64 {
+        self.gears.iter().map(|gear| gear.ratio()).sum()
+    }
+
+    /// The parts for part one, the gears with their two parts for part two.
+    fn details(&self, part: u32) -> Table {
+        if part == 1 {
+            let mut table = Table::new("parts", &["value", "x", "y"]);
+            for engine_part in &self.parts {
+                table.push(vec![
+                    Json::from(engine_part.value()),
+                    Json::from(engine_part.position().x),
+                    Json::from(engine_part.position().y),
+                ]);
+            }
+
+            return table;
+        }
+
+        let mut table = Table::new("gears", &["x", "y", "first", "second", "ratio"]);
+        for gear in &self.gears {
+            table.push(vec![
+                Json::from(gear.symbol.position().x),
+                Json::from(gear.symbol.position().y),
+                Json::from(gear.parts.0.value()),
+                Json::from(gear.parts.1.value()),
+                Json::from(gear.ratio()),
+            ]);
+        }
+
+        table
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{CellKind, TableClassifier};
+    use super::*;
+
+    #[test]
+    fn test_schema_snapshots() {
+        for name in ["test_schema_input", "test_schema_ragged_input"] {
+            let schema = Schema::from_file(&format!("src/schema/{}.txt", name)).unwrap();
+
+            common::assert_snapshot(format!("src/schema/snapshots/{}.snap", name), &schema);
+        }
+    }
+
+    #[test]
+    fn test_ragged_schema() {
+        let testing_schema = Schema::from_file("src/schema/test_schema_ragged_input.txt").unwrap();
+
+        assert_eq!(testing_schema.width(), 7);
+        assert_eq!(testing_schema.height(), 3);
+
+        assert!(testing_schema.has_symbol(Position { x: 2, y: 1 }));
+        assert!(testing_schema.has_symbol(Position { x: 6, y: 2 }));
+        assert_eq!(testing_schema.get(Position { x: 3, y: 1 }), None);
+        assert_eq!(testing_schema.get(Position { x: 5, y: 0 }), None);
+
+        assert_eq!(testing_schema.parts().len(), 2);
+    }
+
+    #[test]
+    fn test_number_elements() {
+        let testing_schema = Schema::from_file("src/schema/test_schema_input.txt").unwrap();
+
+        assert_eq!(testing_schema.elements().len(), 5);
+        assert_eq!(testing_schema.numbers().count(), 2);
+
+        let number = testing_schema.get(Position { x: 0, y: 0 }).unwrap();
+        assert_eq!(number.number(), Some(467));
+        assert_eq!(number.width(), 3);
+        assert_eq!(testing_schema.get(Position { x: 1, y: 0 }), Some(number));
+        assert_eq!(testing_schema.get(Position { x: 2, y: 0 }), Some(number));
+        assert_eq!(
+            testing_schema.element_index(Position { x: 2, y: 0 }),
+            Some(0)
+        );
+
+        let adjacent: Vec<&String> = testing_schema
+            .adjacent(number)
+            .iter()
+            .map(|element| element.value())
+            .collect();
+        assert_eq!(adjacent, vec!["..", "*"]);
+
+        let part_values: Vec<u32> = testing_schema
+            .parts()
+            .iter()
+            .map(|part| part.value())
+            .collect();
+        assert_eq!(part_values, vec![467, 3]);
+    }
+
+    #[test]
+    fn test_gears() {
+        let testing_schema = Schema::from_file("src/bin/test_input_ORIGINAL.txt").unwrap();
+        let gears = testing_schema.gears();
+
+        assert_eq!(gears.len(), 2);
+        assert_eq!(gears[0].symbol.position(), &Position { x: 3, y: 1 });
+        assert_eq!(gears[0].parts.0.value(), 467);
+        assert_eq!(gears[0].parts.1.value(), 35);
+        assert_eq!(gears[0].ratio(), 16345);
+        assert_eq!(gears[1].ratio(), 451490);
+
+        let testing_schema = Schema::from_file("src/schema/test_schema_input.txt").unwrap();
+        assert_eq!(testing_schema.gears().len(), 1);
+        assert_eq!(testing_schema.gears()[0].ratio(), 1401);
+
+        let large_schema: Schema = "99999*99999".parse().unwrap();
+        assert_eq!(large_schema.gears()[0].ratio(), 9_999_800_001);
+        assert_eq!(large_schema.part_two(), 9_999_800_001);
+    }
+
+    #[test]
+    fn test_numbers_across_row_boundaries() {
+        let testing_schema =
+            Schema::from_file("src/schema/test_schema_boundaries_input.txt").unwrap();
+
+        let number_values: Vec<u32> = testing_schema
+            .numbers()
+            .filter_map(|element| element.number())
+            .collect();
+        assert_eq!(number_values, vec![12, 34, 56, 78, 90, 7, 8, 9, 1]);
+
+        let part_values: Vec<u32> = testing_schema
+            .parts()
+            .iter()
+            .map(|part| part.value())
+            .collect();
+        assert_eq!(part_values, vec![34, 56, 78, 90, 8, 1]);
+
+        let bottom_right = testing_schema.get(Position { x: 9, y: 5 }).unwrap();
+        assert_eq!(bottom_right.number(), Some(1));
+        let bottom_left = testing_schema.get(Position { x: 0, y: 5 }).unwrap();
+        assert_eq!(bottom_left.number(), Some(9));
+    }
+
+    #[test]
+    fn test_custom_classifier() {
+        let file_path = "src/schema/test_schema_variant_input.txt";
+
+        let default_values: Vec<u32> = Schema::from_file(file_path)
+            .unwrap()
+            .parts()
+            .iter()
+            .map(|part| part.value())
+            .collect();
+        assert_eq!(default_values, vec![467, 114, 35, 633]);
+
+        let classifier = TableClassifier::new(CellKind::Symbol).blanks("#");
+        let testing_schema = Schema::from_file_with(file_path, &classifier).unwrap();
+        let variant_values: Vec<u32> = testing_schema
+            .parts()
+            .iter()
+            .map(|part| part.value())
+            .collect();
+        assert_eq!(variant_values, vec![633]);
+        assert!(testing_schema.has_symbol(Position { x: 7, y: 3 }));
+        assert!(!testing_schema.has_symbol(Position { x: 3, y: 0 }));
+    }
+
+    #[test]
+    fn test_schema_from_str() {
+        let testing_schema = Schema::from_str("467\n..*\n..3").unwrap();
+
+        assert_eq!(
+            testing_schema,
+            Schema::from_file("src/schema/test_schema_input.txt").unwrap()
+        );
+        assert_eq!(testing_schema.parts().len(), 2);
+
+        let parsed_schema: Schema = "..\n.1".parse().unwrap();
+        assert_eq!(parsed_schema.height(), 2);
+        assert!(parsed_schema.parts().is_empty());
+
+        let empty_schema = Schema::from_str("").unwrap();
+        assert_eq!(empty_schema, Schema::new());
+    }
+
+    #[test]
+    fn test_schema_from_reader() {
+        let content = "467..\n...*.\n".as_bytes();
+        let testing_schema = Schema::from_reader(content).unwrap();
+
+        assert_eq!(testing_schema.width(), 5);
+        assert_eq!(testing_schema.parts().len(), 1);
+    }
+
+    #[test]
+    fn test_schema_errors() {
+        let missing_file = Schema::from_file("src/schema/missing_input.txt");
+        assert!(matches!(missing_file, Err(SchemaError::Io(_))));
+
+        let too_large = Schema::from_str("..*\n.99999999999.");
+        assert_eq!(
+            too_large.as_ref().unwrap_err().to_string(),
+            "line 2, column 2: expected a number up to 4294967295, found '99999999999'"
+        );
+        match too_large {
+            Err(SchemaError::InvalidNumber { position, value }) => {
+                assert_eq!(position, Position { x: 1, y: 1 });
+                assert_eq!(value, "99999999999");
             }
+            other => panic!("expected an invalid number error, got {:?}", other),
         }
+    }
 
-        false
+    #[test]
+    fn test_solution() {
+        let content = fs::read_to_string("src/bin/test_input_ORIGINAL.txt").unwrap();
+        let testing_schema = <Schema as Solution>::parse(&content).unwrap();
+
+        assert_eq!(testing_schema.part_one(), 4361);
+        assert_eq!(testing_schema.part_two(), 467835);
+
+        let large_schema = <Schema as Solution>::parse("4294967295*1").unwrap();
+        assert_eq!(large_schema.part_one(), 4_294_967_296);
+
+        let mut recorder = common::Recorder::default();
+        Schema::from_str_observed(&content, &DefaultClassifier, &mut recorder).unwrap();
+        assert_eq!(
+            recorder.messages(Level::Info),
+            vec![
+                "total lines on input: 10",
+                "total number of parts found: 8",
+                "total number of gears found: 2"
+            ]
+        );
+        assert_eq!(recorder.messages(Level::Debug).len(), 10);
+        assert_eq!(
+            recorder.messages(Level::Debug)[1],
+            "number 114 at (5, 0) is part: false"
+        );
+
+        let parts = testing_schema.details(1);
+        assert_eq!(parts.rows.len(), 8);
+        assert_eq!(parts.rows[0], [467u32, 0, 0].map(Json::from).to_vec());
+
+        let gears = testing_schema.details(2);
+        assert_eq!(gears.columns, vec!["x", "y", "first", "second", "ratio"]);
+        assert_eq!(
+            gears.rows[0],
+            [3u32, 1, 467, 35, 16345].map(Json::from).to_vec()
+        );
     }
 }
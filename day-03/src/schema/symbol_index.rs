@@ -0,0 +1,160 @@
+use std::collections::HashMap;
+
+use super::element::Element;
+use super::grid::{Connectivity, Grid};
+use super::position::Position;
+
+const EMPTY: &[usize] = &[];
+
+/// Symbol adjacency of a schema, computed once from its symbols.
+///
+/// `bitmap` has one bit per cell, set when the cell touches a symbol, `parts`
+/// has one bit per element, set for numbers touching a symbol, and the two
+/// maps link numbers and symbols by element index in both directions.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct SymbolIndex {
+    width: usize,
+    bitmap: Vec<u64>,
+    parts: Vec<u64>,
+    symbols_by_number: HashMap<usize, Vec<usize>>,
+    numbers_by_symbol: HashMap<usize, Vec<usize>>,
+}
+
+impl SymbolIndex {
+    pub fn build(elements: &[Element], index: &Grid<usize>) -> SymbolIndex {
+        let width = index.width();
+        let mut symbol_index = SymbolIndex {
+            width,
+            bitmap: vec![0; (width * index.height()).div_ceil(64)],
+            parts: vec![0; elements.len().div_ceil(64)],
+            symbols_by_number: HashMap::new(),
+            numbers_by_symbol: HashMap::new(),
+        };
+
+        for (symbol, element) in elements.iter().enumerate() {
+            if !element.is_symbol() {
+                continue;
+            }
+
+            for (position, neighbour) in index.neighbours(*element.position(), Connectivity::Eight)
+            {
+                set_bit(&mut symbol_index.bitmap, position.y * width + position.x);
+
+                if elements[*neighbour].is_number() {
+                    symbol_index.link(*neighbour, symbol);
+                }
+            }
+        }
+
+        symbol_index
+    }
+
+    fn link(&mut self, number: usize, symbol: usize) {
+        let symbols = self.symbols_by_number.entry(number).or_default();
+        if symbols.last() == Some(&symbol) {
+            return;
+        }
+        symbols.push(symbol);
+        set_bit(&mut self.parts, number);
+
+        self.numbers_by_symbol
+            .entry(symbol)
+            .or_default()
+            .push(number);
+    }
+
+    /// Whether the cell at `position` is one of the eight neighbours of a symbol.
+    pub fn touches_symbol(&self, position: Position) -> bool {
+        if position.x >= self.width {
+            return false;
+        }
+
+        get_bit(&self.bitmap, position.y * self.width + position.x)
+    }
+
+    pub fn is_part(&self, number: usize) -> bool {
+        get_bit(&self.parts, number)
+    }
+
+    pub fn symbols_touching(&self, number: usize) -> &[usize] {
+        match self.symbols_by_number.get(&number) {
+            Some(symbols) => symbols,
+            None => EMPTY,
+        }
+    }
+
+    pub fn numbers_touching(&self, symbol: usize) -> &[usize] {
+        match self.numbers_by_symbol.get(&symbol) {
+            Some(numbers) => numbers,
+            None => EMPTY,
+        }
+    }
+}
+
+fn set_bit(bits: &mut [u64], bit: usize) {
+    bits[bit / 64] |= 1 << (bit % 64);
+}
+
+fn get_bit(bits: &[u64], bit: usize) -> bool {
+    match bits.get(bit / 64) {
+        Some(word) => word & (1 << (bit % 64)) != 0,
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Schema;
+    use super::*;
+
+    #[test]
+    fn test_symbol_index() {
+        let testing_schema: Schema = "467..114..\n...*......\n..35..633.\n......#...\n617*......"
+            .parse()
+            .unwrap();
+        let symbol_index = SymbolIndex::build(testing_schema.elements(), testing_schema.index());
+
+        assert!(symbol_index.touches_symbol(Position { x: 2, y: 0 }));
+        assert!(symbol_index.touches_symbol(Position { x: 4, y: 2 }));
+        assert!(!symbol_index.touches_symbol(Position { x: 3, y: 1 }));
+        assert!(!symbol_index.touches_symbol(Position { x: 5, y: 0 }));
+        assert!(!symbol_index.touches_symbol(Position { x: 10, y: 0 }));
+        assert!(!symbol_index.touches_symbol(Position { x: 0, y: 9 }));
+
+        let element_index = |x, y| testing_schema.element_index(Position { x, y }).unwrap();
+        let star = element_index(3, 1);
+        let hash = element_index(6, 3);
+        let second_star = element_index(3, 4);
+
+        assert!(symbol_index.is_part(element_index(0, 0)));
+        assert!(!symbol_index.is_part(element_index(5, 0)));
+        assert_eq!(
+            symbol_index.numbers_touching(star),
+            &[element_index(0, 0), element_index(2, 2)]
+        );
+        assert_eq!(symbol_index.numbers_touching(hash), &[element_index(6, 2)]);
+        assert_eq!(
+            symbol_index.numbers_touching(second_star),
+            &[element_index(0, 4)]
+        );
+        assert_eq!(symbol_index.symbols_touching(element_index(2, 2)), &[star]);
+        assert_eq!(symbol_index.symbols_touching(element_index(5, 0)), EMPTY);
+    }
+
+    #[test]
+    fn test_number_touching_several_symbols() {
+        let testing_schema: Schema = "*..\n.5.\n..#".parse().unwrap();
+        let symbol_index = SymbolIndex::build(testing_schema.elements(), testing_schema.index());
+
+        let number = testing_schema
+            .element_index(Position { x: 1, y: 1 })
+            .unwrap();
+        let symbols: Vec<&String> = symbol_index
+            .symbols_touching(number)
+            .iter()
+            .map(|symbol| testing_schema.elements()[*symbol].value())
+            .collect();
+
+        assert_eq!(symbols, vec!["*", "#"]);
+    }
+}
@@ -0,0 +1,160 @@
+use common::Scanner;
+
+use super::classifier::{CellClassifier, CellKind};
+use super::element::{Element, ElementProps};
+use super::error::SchemaError;
+use super::position::Position;
+
+fn element(kind: CellKind, props: ElementProps) -> Result<Element, SchemaError> {
+    Ok(match kind {
+        CellKind::Blank => Element::Dot(props),
+        CellKind::Ignored => Element::Unknown(props),
+        CellKind::Digit => match props.value.parse() {
+            Ok(number) => Element::Number(props, number),
+            Err(_) => {
+                return Err(SchemaError::InvalidNumber {
+                    position: props.position,
+                    value: props.value,
+                })
+            }
+        },
+        CellKind::Symbol => Element::Symbol(props),
+    })
+}
+
+/// Splits a single schema row into elements.
+///
+/// Runs of digits and runs of blanks are lexed as one token each, any other
+/// character is a token of its own. A number runs until the first non-digit
+/// character or the end of the row, so nothing is ever carried over to the
+/// next row. Fails on a run of digits that does not fit in a `u32`.
+pub fn tokenize_row(
+    line_number: usize,
+    line: &str,
+    classifier: &dyn CellClassifier,
+) -> Result<Vec<Element>, SchemaError> {
+    let mut row_elements: Vec<Element> = Vec::new();
+    let mut line_scanner = Scanner::new(line);
+
+    while let Some(character) = line_scanner.peek() {
+        let kind = classifier.classify(character);
+        let position = Position {
+            x: line_scanner.cursor(),
+            y: line_number,
+        };
+
+        let value = match kind {
+            CellKind::Digit | CellKind::Blank => {
+                line_scanner.take_while(|next| classifier.classify(next) == kind)
+            }
+            CellKind::Symbol | CellKind::Ignored => line_scanner.next().into_iter().collect(),
+        };
+
+        row_elements.push(element(
+            kind,
+            ElementProps {
+                position,
+                width: value.chars().count(),
+                value,
+            },
+        )?);
+    }
+
+    Ok(row_elements)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{DefaultClassifier, TableClassifier};
+    use super::*;
+
+    fn values(elements: &[Element]) -> Vec<&str> {
+        elements
+            .iter()
+            .map(|element| element.value().as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_tokenize_row() {
+        let row_elements = tokenize_row(0, "467..114..", &DefaultClassifier).unwrap();
+
+        assert_eq!(values(&row_elements), vec!["467", "..", "114", ".."]);
+        assert_eq!(row_elements[0].number(), Some(467));
+        assert_eq!(row_elements[2].position(), &Position { x: 5, y: 0 });
+        assert_eq!(row_elements[2].width(), 3);
+    }
+
+    #[test]
+    fn test_number_at_row_end() {
+        let row_elements = tokenize_row(3, "..35", &DefaultClassifier).unwrap();
+
+        assert_eq!(values(&row_elements), vec!["..", "35"]);
+        assert_eq!(row_elements[1].number(), Some(35));
+        assert_eq!(row_elements[1].position(), &Position { x: 2, y: 3 });
+
+        let row_elements = tokenize_row(0, "7", &DefaultClassifier).unwrap();
+        assert_eq!(row_elements.len(), 1);
+        assert_eq!(row_elements[0].number(), Some(7));
+    }
+
+    #[test]
+    fn test_number_next_to_symbol() {
+        let row_elements = tokenize_row(0, "617*", &DefaultClassifier).unwrap();
+        assert_eq!(values(&row_elements), vec!["617", "*"]);
+        assert!(row_elements[1].is_symbol());
+
+        let row_elements = tokenize_row(0, "#58.", &DefaultClassifier).unwrap();
+        assert_eq!(values(&row_elements), vec!["#", "58", "."]);
+        assert_eq!(row_elements[1].number(), Some(58));
+
+        let row_elements = tokenize_row(0, "12*34", &DefaultClassifier).unwrap();
+        assert_eq!(values(&row_elements), vec!["12", "*", "34"]);
+    }
+
+    #[test]
+    fn test_symbols_and_letters_are_single_cells() {
+        let row_elements = tokenize_row(0, "**ab", &DefaultClassifier).unwrap();
+
+        assert_eq!(values(&row_elements), vec!["*", "*", "a", "b"]);
+        assert!(matches!(row_elements[2], Element::Unknown(_)));
+    }
+
+    #[test]
+    fn test_empty_row() {
+        assert!(tokenize_row(0, "", &DefaultClassifier).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_tokenize_row_with_classifier() {
+        let classifier = TableClassifier::new(CellKind::Symbol).blanks("#");
+        let row_elements = tokenize_row(0, "12##x3.", &classifier).unwrap();
+
+        assert_eq!(values(&row_elements), vec!["12", "##", "x", "3", "."]);
+        assert!(matches!(row_elements[1], Element::Dot(_)));
+        assert!(row_elements[2].is_symbol());
+        assert!(row_elements[4].is_symbol());
+    }
+
+    #[test]
+    fn test_invalid_number() {
+        match tokenize_row(2, "..99999999999*", &DefaultClassifier) {
+            Err(SchemaError::InvalidNumber { position, value }) => {
+                assert_eq!(position, Position { x: 2, y: 2 });
+                assert_eq!(value, "99999999999");
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        let classifier = |_| CellKind::Digit;
+        assert!(tokenize_row(0, "1a", &classifier).is_err());
+    }
+
+    #[test]
+    fn test_non_ascii_digits_are_not_numbers() {
+        let row_elements = tokenize_row(0, "1٣2", &DefaultClassifier).unwrap();
+
+        assert_eq!(values(&row_elements), vec!["1", "٣", "2"]);
+        assert!(row_elements[1].is_symbol());
+    }
+}